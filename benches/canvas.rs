@@ -0,0 +1,112 @@
+mod common;
+
+use std::time::SystemTime;
+
+use criterion::{criterion_group, BenchmarkId, Criterion, Throughput};
+
+use ray_casting_prototype::canvas;
+use ray_casting_prototype::graphics::color::{Blend, Color};
+use ray_casting_prototype::graphics::line::Line;
+use ray_casting_prototype::graphics::rectangle::Rectangle;
+
+const RESOLUTIONS: [(&str, u32, u32); 3] = [
+    ("720p", 1280, 720),
+    ("1080p", 1920, 1080),
+    ("4k", 3840, 2160),
+];
+
+fn buffer(width: u32, height: u32) -> Vec<u8> {
+    vec![0u8; (width * height * 4) as usize]
+}
+
+fn background(criterion: &mut Criterion) {
+    let color = Color::new(32, 64, 96, 255);
+
+    let mut group = criterion.benchmark_group("background");
+
+    for (name, width, height) in RESOLUTIONS {
+        let mut buffer = buffer(width, height);
+
+        group.throughput(Throughput::Elements((width * height) as u64));
+        group.bench_function(name, |bencher| {
+            bencher.iter(|| canvas::background::render(&mut buffer, &color, Blend::Normal));
+        });
+    }
+
+    group.finish();
+}
+
+fn line(criterion: &mut Criterion) {
+    let (width, height) = (1280, 720);
+    let mut buffer = buffer(width, height);
+    let color = Color::new(255, 255, 255, 255);
+
+    let lines = [
+        ("short", Line::new(100.0, 100.0, 120.0, 110.0)),
+        ("horizontal", Line::new(0.0, 360.0, 1279.0, 360.0)),
+        ("vertical", Line::new(640.0, 0.0, 640.0, 719.0)),
+        ("diagonal", Line::new(0.0, 0.0, 1279.0, 719.0)),
+        ("clipped", Line::new(-400.0, -300.0, 1600.0, 1000.0)),
+    ];
+
+    let mut group = criterion.benchmark_group("line");
+
+    for (name, line) in lines {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &line, |bencher, line| {
+            bencher.iter(|| {
+                canvas::line::render(&mut buffer, width, height, line, &color, Blend::Normal)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn rectangle(criterion: &mut Criterion) {
+    let (width, height) = (1920, 1080);
+    let mut buffer = buffer(width, height);
+    let color = Color::new(255, 128, 0, 255);
+
+    let mut group = criterion.benchmark_group("rectangle");
+
+    for size in [16, 256, 1024] {
+        let solid = Rectangle::new(100.0, 20.0, size as f32, size as f32, true);
+        let outline = Rectangle::new(100.0, 20.0, size as f32, size as f32, false);
+
+        group.bench_with_input(BenchmarkId::new("solid", size), &solid, |bencher, rect| {
+            bencher.iter(|| {
+                canvas::rectangle::render(&mut buffer, width, height, rect, &color, Blend::Normal)
+            });
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("outline", size),
+            &outline,
+            |bencher, rect| {
+                bencher.iter(|| {
+                    canvas::rectangle::render_outline(
+                        &mut buffer,
+                        width,
+                        height,
+                        rect,
+                        &color,
+                        Blend::Normal,
+                    )
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, background, line, rectangle);
+
+fn main() {
+    let start = SystemTime::now();
+
+    benches();
+
+    Criterion::default().configure_from_args().final_summary();
+    common::summary("canvas", start);
+}
@@ -0,0 +1,104 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde_json::{json, Value};
+
+// Directory criterion writes its reports to
+fn criterion_home() -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+
+    env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+        .join("criterion")
+}
+
+// Collect the `new` reports of benchmarks measured since `start`
+fn collect(directory: &Path, start: SystemTime, results: &mut Vec<Value>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        if path.file_name().is_some_and(|name| name == "new") {
+            if let Some(result) = result(&path, start) {
+                results.push(result);
+            }
+        } else {
+            collect(&path, start, results);
+        }
+    }
+}
+
+fn read(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn result(directory: &Path, start: SystemTime) -> Option<Value> {
+    let estimates_path = directory.join("estimates.json");
+
+    let modified = fs::metadata(&estimates_path).ok()?.modified().ok()?;
+
+    if modified < start {
+        return None;
+    }
+
+    let benchmark = read(&directory.join("benchmark.json"))?;
+    let estimates = read(&estimates_path)?;
+
+    let estimate = |name: &str| estimates[name]["point_estimate"].as_f64();
+
+    Some(json!({
+        "id": benchmark["full_id"],
+        "group": benchmark["group_id"],
+        "function": benchmark["function_id"],
+        "parameter": benchmark["value_str"],
+        "throughput": benchmark["throughput"],
+        "mean_ns": estimate("mean"),
+        "median_ns": estimate("median"),
+        "std_dev_ns": estimate("std_dev"),
+        "mean_lower_ns": estimates["mean"]["confidence_interval"]["lower_bound"],
+        "mean_upper_ns": estimates["mean"]["confidence_interval"]["upper_bound"],
+    }))
+}
+
+// Write the estimates of the benchmarks measured in this run to `<criterion home>/<name>.json`
+//
+// Nothing is written when criterion only checked the benchmarks, for example under `cargo test`.
+pub fn summary(name: &str, start: SystemTime) {
+    let home = criterion_home();
+
+    let mut results = Vec::new();
+    collect(&home, start, &mut results);
+
+    if results.is_empty() {
+        return;
+    }
+
+    results.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+
+    let summary = json!({
+        "package": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "bench": name,
+        "benchmarks": results,
+    });
+
+    let path = home.join(format!("{name}.json"));
+
+    let text = serde_json::to_string_pretty(&summary).expect("Summary should serialize");
+
+    match fs::write(&path, text) {
+        Ok(()) => println!("Benchmark summary written to {}", path.display()),
+        Err(error) => eprintln!("Benchmark summary {} not written: {error}", path.display()),
+    }
+}
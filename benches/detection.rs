@@ -0,0 +1,86 @@
+mod common;
+
+use std::f32::consts::PI;
+use std::time::SystemTime;
+
+use criterion::{criterion_group, BenchmarkId, Criterion};
+
+use ray_casting_prototype::app::detection::cast_ray;
+
+const UNIT: f32 = 10.0;
+const MAX: f32 = 720.0;
+
+// Square map sizes in tiles
+const SIZES: [usize; 3] = [16, 64, 256];
+
+// Ray angles, axis aligned and diagonal
+const ANGLES: [(&str, f32); 4] = [
+    ("east", 0.0),
+    ("shallow", PI / 12.0),
+    ("diagonal", PI / 4.0),
+    ("steep", PI * 7.0 / 12.0),
+];
+
+// Walled room with a pillar on every fourth tile
+fn grid(size: usize) -> Vec<Vec<u32>> {
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let border = x == 0 || y == 0 || x == size - 1 || y == size - 1;
+                    let pillar = x % 4 == 2 && y % 4 == 2 && (x + y) % 8 == 4;
+                    u32::from(border || pillar)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn detection(criterion: &mut Criterion) {
+    let heights = [1.0; 16];
+
+    let mut group = criterion.benchmark_group("cast_ray");
+
+    for size in SIZES {
+        let grid = grid(size);
+        let extent = size as f32 * UNIT;
+
+        // Start just off the center, away from the pillars
+        let x = extent / 2.0 + UNIT * 0.5;
+        let y = extent / 2.0 + UNIT * 0.5;
+
+        for (name, angle) in ANGLES {
+            group.bench_with_input(BenchmarkId::new(name, size), &angle, |bencher, angle| {
+                bencher.iter(|| {
+                    cast_ray(
+                        x,
+                        y,
+                        extent,
+                        extent,
+                        *angle,
+                        *angle,
+                        MAX,
+                        UNIT,
+                        &grid,
+                        &[],
+                        &[],
+                        &heights,
+                    )
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, detection);
+
+fn main() {
+    let start = SystemTime::now();
+
+    benches();
+
+    Criterion::default().configure_from_args().final_summary();
+    common::summary("detection", start);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use std::thread;
+use std::time::SystemTime;
+
+use criterion::{criterion_group, BenchmarkId, Criterion};
+
+use ray_casting_prototype::app::logic::{render_graphics, update_state};
+use ray_casting_prototype::app::state::State;
+
+// Headless frame sizes, named by their common label
+const RESOLUTIONS: [(&str, u32, u32); 4] = [
+    ("480p", 640, 480),
+    ("720p", 1280, 720),
+    ("1080p", 1920, 1080),
+    ("4k", 3840, 2160),
+];
+
+fn state(width: u32, height: u32, threads: usize) -> State {
+    let mut state = State::load("maps/textured.map").expect("Benchmark map should load");
+    state.env.width = width;
+    state.env.height = height;
+    state.init();
+    state.conf.threads = threads;
+    update_state(&mut state, 0.0, 0.0, 0.0);
+    state
+}
+
+// Render full frames on one thread, a few threads and every available thread
+fn render(criterion: &mut Criterion) {
+    let available = thread::available_parallelism().map_or(1, |threads| threads.get());
+
+    let mut group = criterion.benchmark_group("render_graphics");
+    group.sample_size(10);
+
+    for (name, width, height) in RESOLUTIONS {
+        let mut buffer = vec![0u8; (width * height * 4) as usize];
+
+        let mut counts = vec![1, 2, 4, available];
+        counts.sort();
+        counts.dedup();
+
+        for threads in counts {
+            let mut state = state(width, height, threads);
+
+            group.bench_with_input(BenchmarkId::new(name, threads), &threads, |bencher, _| {
+                bencher.iter(|| render_graphics(&mut state, &mut buffer, width, height));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, render);
+
+fn main() {
+    let start = SystemTime::now();
+
+    benches();
+
+    Criterion::default().configure_from_args().final_summary();
+    common::summary("render", start);
+}
@@ -1,5 +1,6 @@
 pub mod data;
 pub mod detection;
 pub mod graphics;
+pub mod level;
 pub mod logic;
 pub mod state;
@@ -1,7 +1,7 @@
 use std::f32::consts::PI;
 use std::f32::INFINITY;
 
-use super::state::{Collision, Orientation, Ray};
+use super::state::{Collision, Door, Orientation, Ray, Segment};
 
 // Compute line length
 pub fn magnitude(x0: f32, y0: f32, x1: f32, y1: f32) -> f32 {
@@ -26,115 +26,284 @@ pub fn intersection(x: i32, y: i32, width: i32, height: i32, grid: &Vec<Vec<u32>
         return -1;
     }
 
-    return grid[y as usize][x as usize] as i32;
+    return grid
+        .get(y as usize)
+        .and_then(|row| row.get(x as usize))
+        .map_or(-1, |id| *id as i32);
+}
+
+// Find door at grid intersection
+pub fn door_at(x: i32, y: i32, doors: &[Door]) -> Option<&Door> {
+    return doors.iter().find(|door| door.x == x && door.y == y);
+}
+
+// Get tile id at grid intersection blocking movement, open doors don't block
+fn blocking(x: i32, y: i32, width: i32, height: i32, grid: &Vec<Vec<u32>>, doors: &[Door]) -> i32 {
+    let id = intersection(x, y, width, height, grid);
+
+    if id > 0 && door_at(x, y, doors).is_some_and(|door| !door.closed()) {
+        return 0;
+    }
+
+    return id;
 }
 
 // Check collision with grid tiles
-pub fn collision(x: f32, y: f32, width: f32, height: f32, unit: f32, grid: &Vec<Vec<u32>>) -> i32 {
+pub fn collision(
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    unit: f32,
+    grid: &Vec<Vec<u32>>,
+    doors: &[Door],
+) -> i32 {
     let x = (x / unit).floor() as i32;
     let y = (y / unit).floor() as i32;
 
     let width = (width / unit).floor() as i32;
     let height = (height / unit).floor() as i32;
 
-    return intersection(x, y, width, height, grid);
+    return blocking(x, y, width, height, grid, doors);
+}
+
+// Check circle overlap with non-empty grid tiles
+pub fn circle_collision(
+    x: f32,
+    y: f32,
+    radius: f32,
+    width: f32,
+    height: f32,
+    unit: f32,
+    grid: &Vec<Vec<u32>>,
+    doors: &[Door],
+    segments: &[Segment],
+) -> bool {
+    if segments
+        .iter()
+        .any(|segment| segment.line.distance(x, y) < radius)
+    {
+        return true;
+    }
+
+    let min_x = ((x - radius) / unit).floor() as i32;
+    let max_x = ((x + radius) / unit).floor() as i32;
+    let min_y = ((y - radius) / unit).floor() as i32;
+    let max_y = ((y + radius) / unit).floor() as i32;
+
+    let columns = (width / unit).floor() as i32;
+    let rows = (height / unit).floor() as i32;
+
+    for cell_y in min_y..=max_y {
+        for cell_x in min_x..=max_x {
+            if blocking(cell_x, cell_y, columns, rows, grid, doors) == 0 {
+                continue;
+            }
+
+            let left = cell_x as f32 * unit;
+            let top = cell_y as f32 * unit;
+
+            let near_x = x.clamp(left, left + unit);
+            let near_y = y.clamp(top, top + unit);
+
+            if magnitude(x, y, near_x, near_y) < radius {
+                return true;
+            }
+        }
+    }
+
+    return false;
 }
 
-// Cast ray through grid
+// Move circle through grid, sliding along tiles on each axis independently
+pub fn slide(
+    x: f32,
+    y: f32,
+    delta_x: f32,
+    delta_y: f32,
+    radius: f32,
+    width: f32,
+    height: f32,
+    unit: f32,
+    grid: &Vec<Vec<u32>>,
+    doors: &[Door],
+    segments: &[Segment],
+) -> (f32, f32) {
+    let mut x = x;
+    let mut y = y;
+
+    if !circle_collision(
+        x + delta_x,
+        y,
+        radius,
+        width,
+        height,
+        unit,
+        grid,
+        doors,
+        segments,
+    ) {
+        x += delta_x;
+    }
+
+    if !circle_collision(
+        x,
+        y + delta_y,
+        radius,
+        width,
+        height,
+        unit,
+        grid,
+        doors,
+        segments,
+    ) {
+        y += delta_y;
+    }
+
+    return (x, y);
+}
+
+// Cast ray through grid, returning hits nearest first
 pub fn cast_ray(
     sub_x: f32,
     sub_y: f32,
     map_width: f32,
     map_height: f32,
     direction: f32,
-    sector: f32,
-    arc: f32,
-    index: f32,
+    angle: f32,
     max: f32,
     unit: f32,
     grid: &Vec<Vec<u32>>,
-) -> Ray {
-    let angle = constrain(sector + arc * index);
-
-    let Collision {
-        id,
-        distance,
-        orientation,
-    } = ray_collision(sub_x, sub_y, map_width, map_height, angle, unit, grid);
-
-    let height = ray_height(direction, angle, distance, max, unit);
+    doors: &[Door],
+    segments: &[Segment],
+    heights: &[f32],
+) -> Vec<Ray> {
+    let angle = constrain(angle);
+
+    let collisions = ray_collisions(
+        sub_x, sub_y, map_width, map_height, angle, unit, grid, doors, segments, heights,
+    );
+
+    return collisions
+        .into_iter()
+        .map(|collision| {
+            let Collision {
+                id,
+                distance,
+                orientation,
+                offset,
+                exit,
+                ..
+            } = collision;
+
+            let height = ray_height(direction, angle, distance, max, unit);
+            let exit = ray_height(direction, angle, exit, max, unit);
+            let wall = wall_height(id, heights);
+
+            Ray::new(id, angle, height, distance, orientation, offset, wall, exit)
+        })
+        .collect();
+}
 
-    return Ray::new(id, angle, height, distance, orientation);
+// Get wall height of tile id in units, 1 when not set
+pub fn wall_height(id: i32, heights: &[f32]) -> f32 {
+    return heights.get(id as usize).copied().unwrap_or(1.0);
 }
 
-// Compute ray height
+// Compute projected ray height, exceeding max when close to a wall
 fn ray_height(direction: f32, angle: f32, distance: f32, max: f32, unit: f32) -> f32 {
     let ratio = (direction - angle).cos();
     let length = (distance / unit) * ratio;
     let height = max / length;
 
-    if height < max {
+    if height.is_finite() {
         return height;
     }
 
     return max;
 }
 
-// Check ray collision within map
-fn ray_collision(
-    sub_x: f32,
-    sub_y: f32,
-    map_width: f32,
-    map_height: f32,
-    angle: f32,
-    unit: f32,
-    grid: &Vec<Vec<u32>>,
-) -> Collision {
-    let vertical = collision_vertical(sub_x, sub_y, map_width, map_height, angle, unit, grid);
-    let horizontal = collision_horizontal(sub_x, sub_y, map_width, map_height, angle, unit, grid);
+// Compute hit offset along tile face
+fn face_offset(position: f32, unit: f32, flip: bool) -> f32 {
+    let offset = (position / unit).rem_euclid(1.0);
 
-    return if vertical.distance > horizontal.distance {
-        horizontal
+    if flip {
+        1.0 - offset
     } else {
-        vertical
-    };
+        offset
+    }
 }
 
-// Check horizontal collision
-fn collision_horizontal(
+// Get cell step for ray direction component
+fn grid_step(direction: f32) -> i32 {
+    if direction > 0.0 {
+        return 1;
+    }
+
+    if direction < 0.0 {
+        return -1;
+    }
+
+    return 0;
+}
+
+// Compute distance along ray to the next gridline on one axis
+fn boundary_distance(origin: f32, direction: f32, cell: i32, step: i32, unit: f32) -> f32 {
+    if step == 0 {
+        return INFINITY;
+    }
+
+    let boundary = if step > 0 { cell + 1 } else { cell } as f32 * unit;
+
+    return (boundary - origin) / direction;
+}
+
+// Check ray collision with door recessed to the center of its tile, after entering at distance
+fn door_collision(
     sub_x: f32,
     sub_y: f32,
-    map_width: f32,
-    map_height: f32,
-    angle: f32,
+    direction_x: f32,
+    direction_y: f32,
+    distance: f32,
     unit: f32,
-    grid: &Vec<Vec<u32>>,
-) -> Collision {
-    let tan = angle.tan();
-    let right = angle > (PI * 3.0) / 2.0 || angle < PI / 2.0;
+    door: &Door,
+) -> Option<(f32, Orientation, f32)> {
+    let (origin, direction, cell, across_origin, across_direction, across_cell) = if door.vertical {
+        (sub_x, direction_x, door.x, sub_y, direction_y, door.y)
+    } else {
+        (sub_y, direction_y, door.y, sub_x, direction_x, door.x)
+    };
 
-    let delta_x = unit;
-    let delta_y = unit * tan;
+    if direction == 0.0 {
+        return None;
+    }
 
-    let unit_x = sub_x / unit;
-    let grid_x = if right { unit_x.ceil() } else { unit_x.floor() };
+    let plane = (cell as f32 + 0.5) * unit;
+    let hit = (plane - origin) / direction;
 
-    let map_x = grid_x * unit;
-    let map_y = sub_y + (map_x - sub_x) * tan;
+    if hit < distance {
+        return None;
+    }
 
-    if right {
-        return collision_right(
-            sub_x, sub_y, delta_x, delta_y, map_x, map_y, map_width, map_height, unit, grid,
-        );
-    } else {
-        return collision_left(
-            sub_x, sub_y, delta_x, delta_y, map_x, map_y, map_width, map_height, unit, grid,
-        );
+    // Position along the door plane within the tile, the door slides towards 1 when opening
+    let position = (across_origin + across_direction * hit) / unit - across_cell as f32;
+
+    if position < door.open || position > 1.0 {
+        return None;
     }
+
+    let orientation = match (door.vertical, direction > 0.0) {
+        (true, true) => Orientation::Right,
+        (true, false) => Orientation::Left,
+        (false, true) => Orientation::Up,
+        (false, false) => Orientation::Down,
+    };
+
+    return Some((hit, orientation, position - door.open));
 }
 
-// Check vertical collision
-fn collision_vertical(
+// Check ray collision with grid tiles and wall segments, keeping the nearest
+pub fn ray_collision(
     sub_x: f32,
     sub_y: f32,
     map_width: f32,
@@ -142,170 +311,297 @@ fn collision_vertical(
     angle: f32,
     unit: f32,
     grid: &Vec<Vec<u32>>,
+    doors: &[Door],
+    segments: &[Segment],
 ) -> Collision {
-    let tan = angle.tan();
-    let top = angle > 0.0 && angle < PI;
-
-    let delta_y = unit;
-    let delta_x = unit / tan;
-
-    let unit_y = sub_y / unit;
-    let grid_y = if top { unit_y.ceil() } else { unit_y.floor() };
-
-    let map_y = grid_y * unit;
-    let map_x = sub_x - (sub_y - map_y) / tan;
-
-    if top {
-        return collision_top(
-            sub_x, sub_y, delta_x, delta_y, map_x, map_y, map_width, map_height, unit, grid,
-        );
-    } else {
-        return collision_bottom(
-            sub_x, sub_y, delta_x, delta_y, map_x, map_y, map_width, map_height, unit, grid,
-        );
-    }
+    return ray_collisions(
+        sub_x,
+        sub_y,
+        map_width,
+        map_height,
+        angle,
+        unit,
+        grid,
+        doors,
+        segments,
+        &[],
+    )
+    .into_iter()
+    .next()
+    .unwrap_or(Collision::new(
+        -1,
+        INFINITY,
+        Orientation::None,
+        0.0,
+        sub_x,
+        sub_y,
+        -1,
+        -1,
+    ));
 }
 
-// Check left collision
-fn collision_left(
+// Check ray collisions with grid tiles and wall segments nearest first,
+// passing walls lower than the tallest one until a wall hides everything behind it
+pub fn ray_collisions(
     sub_x: f32,
     sub_y: f32,
-    delta_x: f32,
-    delta_y: f32,
-    map_x: f32,
-    map_y: f32,
     map_width: f32,
     map_height: f32,
+    angle: f32,
     unit: f32,
     grid: &Vec<Vec<u32>>,
-) -> Collision {
-    let mut index = 0.0;
-
-    loop {
-        let tile_x = map_x - delta_x * index;
-        let tile_y = map_y - delta_y * index;
-
-        let id = collision(tile_x - unit, tile_y, map_width, map_height, unit, &grid);
+    doors: &[Door],
+    segments: &[Segment],
+    heights: &[f32],
+) -> Vec<Collision> {
+    let tallest = heights.iter().copied().fold(1.0, f32::max);
+
+    let mut collisions = grid_collisions(
+        sub_x, sub_y, map_width, map_height, angle, unit, grid, doors, heights, tallest,
+    );
+
+    collisions.extend(segment_collisions(sub_x, sub_y, angle, unit, segments));
+    collisions.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+    if let Some(index) = collisions
+        .iter()
+        .position(|collision| wall_height(collision.id, heights) >= tallest)
+    {
+        collisions.truncate(index + 1);
+    }
 
-        if id > 0 {
-            let distance = magnitude(sub_x, sub_y, tile_x, tile_y);
-            return Collision::new(id, distance, Orientation::Left);
-        }
+    return collisions;
+}
 
-        index += 1.0;
+// Check ray collision with wall segments
+pub fn segment_collision(
+    sub_x: f32,
+    sub_y: f32,
+    angle: f32,
+    unit: f32,
+    segments: &[Segment],
+) -> Collision {
+    return segment_collisions(sub_x, sub_y, angle, unit, segments)
+        .into_iter()
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+        .unwrap_or(Collision::new(
+            -1,
+            INFINITY,
+            Orientation::None,
+            0.0,
+            sub_x,
+            sub_y,
+            -1,
+            -1,
+        ));
+}
 
-        if map_x - delta_x * index < 0.0 {
-            break;
-        }
+// Check ray collisions with every wall segment
+fn segment_collisions(
+    sub_x: f32,
+    sub_y: f32,
+    angle: f32,
+    unit: f32,
+    segments: &[Segment],
+) -> Vec<Collision> {
+    let direction_x = angle.cos();
+    let direction_y = angle.sin();
+
+    let mut collisions = Vec::new();
+
+    for segment in segments.iter() {
+        let line = segment.line;
+
+        let Some((distance, position)) = line.intersect(sub_x, sub_y, direction_x, direction_y)
+        else {
+            continue;
+        };
+
+        // Shade like the closest axis aligned face, facing against the ray
+        let steep = (line.x1 - line.x0).abs() < (line.y1 - line.y0).abs();
+
+        let orientation = match (steep, direction_x > 0.0, direction_y > 0.0) {
+            (true, true, _) => Orientation::Right,
+            (true, false, _) => Orientation::Left,
+            (false, _, true) => Orientation::Up,
+            (false, _, false) => Orientation::Down,
+        };
+
+        let x = sub_x + direction_x * distance;
+        let y = sub_y + direction_y * distance;
+
+        // Repeat textures once per unit along the segment
+        let offset = (position * line.length() / unit).rem_euclid(1.0);
+
+        let cell_x = (x / unit).floor() as i32;
+        let cell_y = (y / unit).floor() as i32;
+
+        collisions.push(Collision::new(
+            segment.id as i32,
+            distance,
+            orientation,
+            offset,
+            x,
+            y,
+            cell_x,
+            cell_y,
+        ));
     }
 
-    return Collision::new(-1, INFINITY, Orientation::None);
+    return collisions;
 }
 
-// Check right collision
-fn collision_right(
+// Check ray collision within map by traversing grid cells in order
+pub fn grid_collision(
     sub_x: f32,
     sub_y: f32,
-    delta_x: f32,
-    delta_y: f32,
-    map_x: f32,
-    map_y: f32,
     map_width: f32,
     map_height: f32,
+    angle: f32,
     unit: f32,
     grid: &Vec<Vec<u32>>,
+    doors: &[Door],
 ) -> Collision {
-    let mut index = 0.0;
-
-    loop {
-        let tile_x = map_x + delta_x * index;
-        let tile_y = map_y + delta_y * index;
-
-        let id = collision(tile_x, tile_y, map_width, map_height, unit, &grid);
-
-        if id > 0 {
-            let distance = magnitude(sub_x, sub_y, tile_x, tile_y);
-            return Collision::new(id, distance, Orientation::Right);
-        }
-
-        index += 1.0;
-
-        if map_x + delta_x * index > map_width {
-            break;
-        }
-    }
-
-    return Collision::new(-1, INFINITY, Orientation::None);
+    return grid_collisions(
+        sub_x,
+        sub_y,
+        map_width,
+        map_height,
+        angle,
+        unit,
+        grid,
+        doors,
+        &[],
+        1.0,
+    )
+    .into_iter()
+    .next()
+    .unwrap_or(Collision::new(
+        -1,
+        INFINITY,
+        Orientation::None,
+        0.0,
+        sub_x,
+        sub_y,
+        -1,
+        -1,
+    ));
 }
 
-// Check top collision
-fn collision_top(
+// Check ray collisions within map by traversing grid cells in order,
+// stopping at the first wall at least as tall as the tallest wall
+fn grid_collisions(
     sub_x: f32,
     sub_y: f32,
-    delta_x: f32,
-    delta_y: f32,
-    map_x: f32,
-    map_y: f32,
     map_width: f32,
     map_height: f32,
+    angle: f32,
     unit: f32,
     grid: &Vec<Vec<u32>>,
-) -> Collision {
-    let mut index = 0.0;
+    doors: &[Door],
+    heights: &[f32],
+    tallest: f32,
+) -> Vec<Collision> {
+    let mut collisions = Vec::new();
+
+    let width = (map_width / unit).floor() as i32;
+    let height = (map_height / unit).floor() as i32;
+
+    let direction_x = angle.cos();
+    let direction_y = angle.sin();
+
+    let step_x = grid_step(direction_x);
+    let step_y = grid_step(direction_y);
+
+    let mut cell_x = (sub_x / unit).floor() as i32;
+    let mut cell_y = (sub_y / unit).floor() as i32;
 
     loop {
-        let tile_x = map_x + delta_x * index;
-        let tile_y = map_y + delta_y * index;
+        let side_x = boundary_distance(sub_x, direction_x, cell_x, step_x, unit);
+        let side_y = boundary_distance(sub_y, direction_y, cell_y, step_y, unit);
 
-        let id = collision(tile_x, tile_y, map_width, map_height, unit, &grid);
+        let (distance, orientation) = if side_x < side_y {
+            cell_x += step_x;
 
-        if id > 0 {
-            let distance = magnitude(sub_x, sub_y, tile_x, tile_y);
-            return Collision::new(id, distance, Orientation::Up);
-        }
+            let orientation = if step_x > 0 {
+                Orientation::Right
+            } else {
+                Orientation::Left
+            };
+
+            (side_x, orientation)
+        } else {
+            cell_y += step_y;
 
-        index += 1.0;
+            let orientation = if step_y > 0 {
+                Orientation::Up
+            } else {
+                Orientation::Down
+            };
 
-        if map_y + delta_y * index > map_height {
+            (side_y, orientation)
+        };
+
+        let outside_x = (step_x > 0 && cell_x >= width) || (step_x < 0 && cell_x < 0);
+        let outside_y = (step_y > 0 && cell_y >= height) || (step_y < 0 && cell_y < 0);
+
+        if !distance.is_finite() || outside_x || outside_y {
             break;
         }
-    }
 
-    return Collision::new(-1, INFINITY, Orientation::None);
-}
+        let id = intersection(cell_x, cell_y, width, height, grid);
 
-// Check bottom collision
-fn collision_bottom(
-    sub_x: f32,
-    sub_y: f32,
-    delta_x: f32,
-    delta_y: f32,
-    map_x: f32,
-    map_y: f32,
-    map_width: f32,
-    map_height: f32,
-    unit: f32,
-    grid: &Vec<Vec<u32>>,
-) -> Collision {
-    let mut index = 0.0;
+        if let Some(door) = door_at(cell_x, cell_y, doors).filter(|_| id > 0) {
+            let hit = door_collision(sub_x, sub_y, direction_x, direction_y, distance, unit, door);
 
-    loop {
-        let tile_x = map_x - delta_x * index;
-        let tile_y = map_y - delta_y * index;
+            if let Some((distance, orientation, offset)) = hit {
+                let x = sub_x + direction_x * distance;
+                let y = sub_y + direction_y * distance;
 
-        let id = collision(tile_x, tile_y - unit, map_width, map_height, unit, &grid);
+                collisions.push(Collision::new(
+                    id,
+                    distance,
+                    orientation,
+                    offset,
+                    x,
+                    y,
+                    cell_x,
+                    cell_y,
+                ));
 
-        if id > 0 {
-            let distance = magnitude(sub_x, sub_y, tile_x, tile_y);
-            return Collision::new(id, distance, Orientation::Down);
+                if wall_height(id, heights) >= tallest {
+                    break;
+                }
+            }
+
+            continue;
         }
 
-        index += 1.0;
+        if id > 0 {
+            let x = sub_x + direction_x * distance;
+            let y = sub_y + direction_y * distance;
 
-        if map_y - delta_y * index < 0.0 {
-            break;
+            let offset = match orientation {
+                Orientation::Left => face_offset(y, unit, true),
+                Orientation::Right => face_offset(y, unit, false),
+                Orientation::Up => face_offset(x, unit, true),
+                _ => face_offset(x, unit, false),
+            };
+
+            let mut collision =
+                Collision::new(id, distance, orientation, offset, x, y, cell_x, cell_y);
+
+            let exit_x = boundary_distance(sub_x, direction_x, cell_x, step_x, unit);
+            let exit_y = boundary_distance(sub_y, direction_y, cell_y, step_y, unit);
+
+            collision.exit = exit_x.min(exit_y);
+            collisions.push(collision);
+
+            if wall_height(id, heights) >= tallest {
+                break;
+            }
         }
     }
 
-    return Collision::new(-1, INFINITY, Orientation::None);
+    return collisions;
 }
@@ -1,11 +1,23 @@
+use std::f32::consts::PI;
+use std::iter;
+use std::thread;
+
 use crate::canvas;
 
-use crate::graphics::color::Color;
+use crate::graphics::circle::Circle;
+use crate::graphics::color::{Blend, Color};
 use crate::graphics::line::Line;
+use crate::graphics::point::Point;
+use crate::graphics::polygon::{Fill, Polygon};
 use crate::graphics::rectangle::Rectangle;
+use crate::graphics::text::{Align, Text};
+use crate::graphics::texture::Texture;
 
-use super::detection::cast_ray;
-use super::state::{Configuration, Environment, Map, Orientation, Ray, State, Subject, Type};
+use super::data::{CONE, CONE_SEGMENTS, MARGIN, PADDING, SHADE, TEXT_SCALE};
+use super::detection::{cast_ray, door_at, magnitude};
+use super::state::{
+    Configuration, Environment, Fog, Map, Orientation, Plane, Projection, Ray, State, Subject, Type,
+};
 
 // Render rectangle
 pub fn draw_rectangle(
@@ -19,7 +31,56 @@ pub fn draw_rectangle(
     color: &Color,
 ) {
     let rectangle = Rectangle::new(x, y, w, h, true);
-    canvas::rectangle::render(buffer, width, height, &rectangle, color);
+    canvas::rectangle::render(buffer, width, height, &rectangle, color, Blend::Normal);
+}
+
+// Get horizon shift in pixels for the subject pitch, shearing the scene vertically
+pub fn shear(state: &State) -> f32 {
+    state.env.height as f32 * state.sub.pitch.tan()
+}
+
+// Get view angle of a surface ray column for the camera projection
+pub fn column_angle(state: &State, index: f32) -> f32 {
+    let Subject {
+        sector, dir, plane, ..
+    } = state.sub;
+
+    match state.conf.projection {
+        Projection::Angular => sector + state.conf.arc * index,
+        Projection::CameraPlane => {
+            let camera = 2.0 * index / state.conf.resolution as f32 - 1.0;
+            let x = dir.x + plane.x * camera;
+            let y = dir.y + plane.y * camera;
+
+            y.atan2(x)
+        }
+    }
+}
+
+// Get surface ray column of a view angle relative to the subject direction
+pub fn angle_column(state: &State, relative: f32) -> f32 {
+    let Configuration {
+        fov,
+        arc,
+        resolution,
+        projection,
+        ..
+    } = state.conf;
+
+    match projection {
+        Projection::Angular => (relative + fov / 2.0) / arc,
+        Projection::CameraPlane => {
+            (relative.tan() / (fov / 2.0).tan() + 1.0) / 2.0 * resolution as f32
+        }
+    }
+}
+
+// Get horizon row within the surface
+fn horizon_row(state: &State) -> u32 {
+    let max = state.env.height;
+    let horizon = max as f32 / 2.0 + shear(state);
+
+    (horizon.round().max(0.0) as u32).min(max)
 }
 
 // Render scene background
@@ -32,26 +93,142 @@ pub fn draw_surface(state: &mut State, buffer: &mut [u8], width: u32, height: u3
         ..
     } = state.env;
 
-    draw_rectangle(
-        buffer,
-        width,
-        height,
-        x as f32,
-        y as f32,
-        w as f32,
-        (h / 2) as f32,
-        &state.colors.ceiling,
-    );
+    let fog = state.conf.fog.active();
+    let horizon = horizon_row(state);
+
+    if state.conf.ceiling.is_empty() && !fog {
+        draw_rectangle(
+            buffer,
+            width,
+            height,
+            x as f32,
+            y as f32,
+            w as f32,
+            horizon as f32,
+            &state.colors.ceiling,
+        );
+    } else {
+        draw_surface_plane(state, buffer, width, height, Plane::Ceiling);
+    }
 
-    draw_rectangle(
+    if state.conf.floor.is_empty() && !fog {
+        draw_rectangle(
+            buffer,
+            width,
+            height,
+            x as f32,
+            (y + horizon) as f32,
+            w as f32,
+            (h - horizon) as f32,
+            &state.colors.floor,
+        );
+    } else {
+        draw_surface_plane(state, buffer, width, height, Plane::Floor);
+    }
+}
+
+// Get floor or ceiling color at map position in tile units
+fn plane_color(state: &State, grid: &[Vec<u32>], x: f32, y: f32, fallback: &Color) -> Color {
+    let id = grid
+        .get(y.floor() as usize)
+        .and_then(|row| row.get(x.floor() as usize))
+        .copied()
+        .unwrap_or(0);
+
+    if id == 0 || x < 0.0 || y < 0.0 {
+        return *fallback;
+    }
+
+    match state.textures.walls.get(&id) {
+        Some(texture) => texture.sample(x, y),
+        None => state.colors.list[id as usize].base,
+    }
+}
+
+// Cast and render floor or ceiling rows
+pub fn draw_surface_plane(state: &State, buffer: &mut [u8], width: u32, height: u32, plane: Plane) {
+    let Configuration {
+        ratio,
+        resolution,
+        ref floor,
+        ref ceiling,
+        ref fog,
+        ..
+    } = state.conf;
+
+    let Environment {
+        x: env_x,
+        y: env_y,
+        width: env_width,
+        height: max,
+        unit,
+        ..
+    } = state.env;
+
+    let Subject {
+        x: sub_x,
+        y: sub_y,
+        direction,
+        ..
+    } = state.sub;
+
+    let eye = state.sub.eye_height();
+    let row = horizon_row(state);
+
+    // Plane height relative to the eye, scaled to the surface
+    let (grid, fallback, rows, elevation) = match plane {
+        Plane::Floor => (floor, state.colors.floor, row..max, eye),
+        Plane::Ceiling => (ceiling, state.colors.ceiling, 0..row, 1.0 - eye),
+    };
+
+    let horizon = max as f32 / 2.0 + shear(state);
+    let elevation = elevation * max as f32;
+
+    // Ray direction per column, scaled by the inverse fisheye correction
+    let columns: Vec<(f32, f32)> = (0..resolution.max(1))
+        .map(|index| {
+            let angle = column_angle(state, index as f32);
+            let ratio = (direction - angle).cos();
+            (angle.cos() / ratio, angle.sin() / ratio)
+        })
+        .collect();
+
+    draw_bands(
         buffer,
         width,
         height,
-        x as f32,
-        (y + height / 2) as f32,
-        w as f32,
-        (h / 2) as f32,
-        &state.colors.floor,
+        state.conf.threads,
+        |band, first, count| {
+            // Plane rows falling inside the band
+            let start = rows.start.max(first.saturating_sub(env_y));
+            let end = rows.end.min((first + count).saturating_sub(env_y));
+
+            for row in start..end {
+                let offset = (row as f32 + 0.5 - horizon).abs();
+                let distance = elevation / offset;
+
+                for column in 0..env_width {
+                    let index = ((column / ratio) as usize).min(columns.len() - 1);
+                    let (dx, dy) = columns[index];
+
+                    let x = sub_x / unit + dx * distance;
+                    let y = sub_y / unit + dy * distance;
+
+                    let color = plane_color(state, grid, x, y, &fallback);
+                    let color = fog.apply(color, dx.hypot(dy) * distance * unit);
+
+                    canvas::point::plot(
+                        band,
+                        width as usize,
+                        count as usize,
+                        &color,
+                        (env_x + column) as usize,
+                        (env_y + row - first) as usize,
+                        Blend::Normal,
+                    );
+                }
+            }
+        },
     );
 }
 
@@ -61,7 +238,7 @@ pub fn draw_map(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
 
     let grid = &state.conf.grid;
 
-    let Environment { unit, map, .. } = state.env;
+    let env = &state.env;
 
     let Map {
         x: map_x,
@@ -69,7 +246,10 @@ pub fn draw_map(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
         width: map_width,
         height: map_height,
         ..
-    } = map;
+    } = env.map;
+
+    // Tile size on the minimap
+    let unit = env.unit * env.scale;
 
     draw_rectangle(
         buffer,
@@ -77,8 +257,8 @@ pub fn draw_map(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
         height,
         map_x,
         map_y,
-        map_width,
-        map_height,
+        map_width * env.scale,
+        map_height * env.scale,
         &background,
     );
 
@@ -89,33 +269,166 @@ pub fn draw_map(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
 
             let color = state.colors.list[*id as usize].base;
 
-            if *id > 0 {
-                draw_rectangle(buffer, width, height, x, y, unit, unit, &color);
+            if *id == 0 {
+                continue;
+            }
+
+            match door_at(grid_x as i32, grid_y as i32, &state.doors) {
+                // Draw door as thin slab through the tile center, shrinking as it opens
+                Some(door) => {
+                    let thickness = unit / 5.0;
+                    let middle = unit / 2.0 - thickness / 2.0;
+                    let length = unit * (1.0 - door.open);
+                    let offset = unit * door.open;
+
+                    let (x, y, w, h) = if door.vertical {
+                        (x + middle, y + offset, thickness, length)
+                    } else {
+                        (x + offset, y + middle, length, thickness)
+                    };
+
+                    draw_rectangle(buffer, width, height, x, y, w, h, &color);
+                }
+                None => draw_rectangle(buffer, width, height, x, y, unit, unit, &color),
             }
         }
     }
+
+    for segment in state.segments.iter() {
+        let Line { x0, y0, x1, y1 } = segment.line;
+
+        let start = env.minimap(x0, y0);
+        let end = env.minimap(x1, y1);
+
+        let line = Line::new(start.x, start.y, end.x, end.y);
+        let color = state.colors.list[segment.id as usize].base;
+
+        canvas::line::render(buffer, width, height, &line, &color, Blend::Normal);
+    }
+
+    let size = unit / 2.0;
+
+    for sprite in state.sprites.iter() {
+        let center = env.minimap(sprite.x, sprite.y);
+        let x = center.x - size / 2.0;
+        let y = center.y - size / 2.0;
+
+        draw_rectangle(
+            buffer,
+            width,
+            height,
+            x,
+            y,
+            size,
+            size,
+            &state.colors.sprite,
+        );
+    }
 }
 
 // Generate and render player
 pub fn draw_subject(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
     let color = state.colors.player;
 
-    let Map {
-        x: map_x, y: map_y, ..
-    } = state.env.map;
+    let env = &state.env;
 
     let Subject {
         x: sub_x,
         y: sub_y,
         radius,
+        direction,
         ..
     } = state.sub;
 
-    let x = map_x + sub_x - radius;
-    let y = map_y + sub_y - radius;
-    let r = 2.0 * radius;
+    let fov = state.conf.fov;
+    let length = CONE * env.unit * env.scale;
+
+    // Translucent wedge from the player along the field of view, its far edge an arc
+    let center = env.minimap(sub_x, sub_y);
+    let arc = (0..=CONE_SEGMENTS).map(|segment| {
+        let angle = direction - fov / 2.0 + fov * segment as f32 / CONE_SEGMENTS as f32;
+        Point::new(
+            center.x + length * angle.cos(),
+            center.y + length * angle.sin(),
+        )
+    });
+
+    let cone = Polygon::new(iter::once(center).chain(arc).collect(), Fill::EvenOdd, true);
+
+    canvas::polygon::render(
+        buffer,
+        width,
+        height,
+        &cone,
+        &state.colors.cone,
+        Blend::Normal,
+    );
+
+    let circle = Circle::new(center.x, center.y, radius * env.scale, true);
+
+    canvas::circle::render(buffer, width, height, &circle, &color, Blend::Normal);
+}
+
+// Render text on a translucent panel
+pub fn draw_label(state: &State, buffer: &mut [u8], width: u32, height: u32, text: &Text) {
+    let bounds = text.bounds();
+
+    let panel = Rectangle::new(
+        bounds.x - PADDING,
+        bounds.y - PADDING,
+        bounds.width + PADDING * 2.0,
+        bounds.height + PADDING * 2.0,
+        true,
+    );
+
+    canvas::rectangle::render(
+        buffer,
+        width,
+        height,
+        &panel,
+        &state.colors.panel,
+        Blend::Normal,
+    );
+    canvas::text::render(
+        buffer,
+        width,
+        height,
+        text,
+        &state.colors.text,
+        Blend::Normal,
+    );
+}
+
+// Render frame rate and subject tile coordinates in the top left corner, and the message centered at the top
+pub fn draw_overlay(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
+    let unit = state.env.unit;
+
+    let status = format!(
+        "FPS {:.0}\nX {:.1} Y {:.1}",
+        state.conf.fps,
+        state.sub.x / unit,
+        state.sub.y / unit
+    );
+
+    let offset = MARGIN + PADDING;
 
-    draw_rectangle(buffer, width, height, x, y, r, r, &color);
+    let text = Text::new(offset, offset, status, TEXT_SCALE, Align::Left);
+
+    draw_label(state, buffer, width, height, &text);
+
+    if state.message.visible() {
+        let center = (state.env.x + state.env.width / 2) as f32;
+
+        let text = Text::new(
+            center,
+            offset,
+            state.message.text.clone(),
+            TEXT_SCALE,
+            Align::Center,
+        );
+
+        draw_label(state, buffer, width, height, &text);
+    }
 }
 
 // Generate and render map ray
@@ -124,20 +437,16 @@ pub fn draw_map_ray(
     width: u32,
     height: u32,
     length: f32,
-    map_x: f32,
-    map_y: f32,
-    sub_x: f32,
-    sub_y: f32,
+    x0: f32,
+    y0: f32,
     angle: f32,
     color: &Color,
 ) {
-    let x0 = map_x + sub_x;
-    let y0 = map_y + sub_y;
     let x1 = x0 + length * angle.cos();
     let y1 = y0 + length * angle.sin();
 
     let line = Line::new(x0, y0, x1, y1);
-    canvas::line::render(buffer, width, height, &line, color);
+    canvas::line::render_smooth(buffer, width, height, &line, color, Blend::Normal);
 }
 
 // Generate and render scene ray
@@ -147,103 +456,310 @@ pub fn draw_surface_ray(
     height: u32,
     env_x: f32,
     env_y: f32,
+    top: f32,
     length: f32,
     max: f32,
     index: u32,
     ratio: u32,
+    first: u32,
     base: &Color,
     shade: &Color,
     orientation: Orientation,
 ) {
+    let start = top.max(env_y);
+    let length = (length - (start - top)).min(env_y + max - start);
+
+    if length <= 0.0 {
+        return;
+    }
+
     let ray_x = (env_x + (index * ratio) as f32) as i32;
-    let ray_y = (env_y + (max - length) / 2.0) as i32;
 
     let horizontal = orientation == Orientation::Left || orientation == Orientation::Right;
     let color = if horizontal { base } else { shade };
 
-    canvas::line::vertical(buffer, width, height, ray_x, ray_y, length as u32, color);
+    canvas::line::vertical(
+        buffer,
+        width,
+        height,
+        ray_x,
+        start as i32 - first as i32,
+        length as u32,
+        color,
+        Blend::Normal,
+    );
+}
+
+// Generate and render textured scene ray
+pub fn draw_surface_texture_ray(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    env_x: f32,
+    top: f32,
+    length: f32,
+    wall: f32,
+    index: u32,
+    ratio: u32,
+    first: u32,
+    texture: &Texture,
+    offset: f32,
+    distance: f32,
+    orientation: Orientation,
+    fog: &Fog,
+) {
+    let ray_x = (env_x + (index * ratio) as f32) as i32;
+
+    let horizontal = orientation == Orientation::Left || orientation == Orientation::Right;
+    let brightness = if horizontal { 1.0 } else { SHADE };
+
+    let filter = |color: Color| fog.apply(color.scale(brightness), distance);
+
+    canvas::texture::column(
+        buffer, width, height, ray_x, top, length, texture, offset, wall, first, &filter,
+    );
 }
 
-// Generate and render rays
-pub fn draw_rays(state: &mut State, buffer: &mut [u8], width: u32, height: u32, kind: Type) {
+// Render the rays cast for the frame
+pub fn draw_rays(state: &State, buffer: &mut [u8], width: u32, height: u32, kind: Type) {
+    let rays = &state.frame.rays;
+
+    if kind == Type::All || kind == Type::Map {
+        let env = &state.env;
+
+        let origin = env.minimap(state.sub.x, state.sub.y);
+
+        for nearest in rays.iter().filter_map(|rays| rays.first()) {
+            let color = state.colors.list[nearest.id as usize].tint;
+
+            draw_map_ray(
+                buffer,
+                width,
+                height,
+                nearest.distance * env.scale,
+                origin.x,
+                origin.y,
+                nearest.angle,
+                &color,
+            );
+        }
+    }
+
+    if kind != Type::All && kind != Type::Surface {
+        return;
+    }
+
+    draw_bands(
+        buffer,
+        width,
+        height,
+        state.conf.threads,
+        |band, first, rows| {
+            for (index, rays) in rays.iter().enumerate() {
+                draw_column(state, band, width, rows, first, index as u32, rays);
+            }
+        },
+    );
+}
+
+// Cast the rays of every surface column, splitting the columns across threads
+pub fn cast_rays(state: &State) -> Vec<Vec<Ray>> {
     let Configuration {
         ref grid,
-        arc,
-        ratio,
         resolution,
+        ref heights,
+        threads,
         ..
     } = state.conf;
 
     let Environment {
-        x: env_x,
-        y: env_y,
         height: max,
         unit,
         map,
         ..
     } = state.env;
 
-    let Map {
-        x: map_x,
-        y: map_y,
-        width: map_width,
-        height: map_height,
-        ..
-    } = map;
-
     let Subject {
         x: sub_x,
         y: sub_y,
-        sector,
         direction,
         ..
     } = state.sub;
 
-    for index in 0..resolution {
-        let ray = cast_ray(
+    let cast = |index: u32| {
+        cast_ray(
             sub_x,
             sub_y,
-            map_width,
-            map_height,
+            map.width,
+            map.height,
             direction,
-            sector,
-            arc,
-            index as f32,
+            column_angle(state, index as f32),
             max as f32,
             unit,
             grid,
-        );
+            &state.doors,
+            &state.segments,
+            heights,
+        )
+    };
+
+    if threads <= 1 {
+        return (0..resolution).map(cast).collect();
+    }
+
+    let size = resolution.div_ceil(threads as u32).max(1);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..resolution)
+            .step_by(size as usize)
+            .map(|start| {
+                let cast = &cast;
+                let end = (start + size).min(resolution);
+
+                scope.spawn(move || (start..end).map(cast).collect::<Vec<_>>())
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Ray casting thread should not panic"))
+            .collect()
+    })
+}
+
+// Split the buffer into disjoint bands of rows drawn on separate threads, passing each band
+// with its first row and number of rows
+pub fn draw_bands(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    threads: usize,
+    draw: impl Fn(&mut [u8], u32, u32) + Sync,
+) {
+    let stride = width as usize * 4;
+    let buffer = &mut buffer[..stride * height as usize];
+
+    if threads <= 1 || stride == 0 {
+        draw(buffer, 0, height);
+        return;
+    }
+
+    let rows = (height as usize).div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        for (index, band) in buffer.chunks_mut(stride * rows).enumerate() {
+            let draw = &draw;
+            let first = (index * rows) as u32;
+            let count = (band.len() / stride) as u32;
+
+            scope.spawn(move || draw(band, first, count));
+        }
+    });
+}
+
+// Render the rays of a surface column into a band of `rows` rows starting at row `first`
+fn draw_column(
+    state: &State,
+    band: &mut [u8],
+    width: u32,
+    rows: u32,
+    first: u32,
+    index: u32,
+    rays: &[Ray],
+) {
+    let Configuration { ratio, fog, .. } = state.conf;
+
+    let Environment {
+        x: env_x,
+        y: env_y,
+        height: max,
+        ..
+    } = state.env;
+
+    let shift = shear(state);
+    let eye = state.sub.eye_height();
 
+    let env_y = env_y as f32;
+    let horizon = env_y + max as f32 / 2.0 + shift;
+
+    // Draw back to front so nearer walls cover farther ones
+    for ray in rays.iter().rev() {
         let Ray {
             id,
-            angle,
             height: ray_height,
             distance,
             orientation,
-        } = ray;
+            offset,
+            wall,
+            exit,
+            ..
+        } = *ray;
 
-        if id > 0 && (kind == Type::All || kind == Type::Map) {
-            let color = state.colors.list[id as usize].tint;
+        let colors = state.colors.list[id as usize];
 
-            draw_map_ray(
-                buffer, width, height, distance, map_x, map_y, sub_x, sub_y, angle, &color,
+        // Wall bottoms rest on the floor, the eye height times the projected unit height
+        // below the horizon
+        let length = ray_height * wall;
+        let top = env_y + (max as f32 - ray_height) / 2.0 + shift + ray_height * (eye + 0.5 - wall);
+
+        // Top face seen from above, between the wall top at tile entry and exit
+        let far = horizon + exit * (eye - wall);
+
+        if far < top {
+            let color = fog.apply(colors.tint, distance);
+
+            draw_surface_ray(
+                band,
+                width,
+                rows,
+                env_x as f32,
+                env_y,
+                far,
+                top - far,
+                max as f32,
+                index,
+                ratio,
+                first,
+                &color,
+                &color,
+                orientation,
             );
         }
 
-        if id > 0 && (kind == Type::All || kind == Type::Surface) {
-            let color_base = state.colors.list[id as usize].base;
-            let color_shade = state.colors.list[id as usize].shade;
+        if let Some(texture) = state.textures.walls.get(&(id as u32)) {
+            draw_surface_texture_ray(
+                band,
+                width,
+                rows,
+                env_x as f32,
+                top,
+                length,
+                wall,
+                index,
+                ratio,
+                first,
+                texture,
+                offset,
+                distance,
+                orientation,
+                &fog,
+            );
+        } else {
+            let color_base = fog.apply(colors.base, distance);
+            let color_shade = fog.apply(colors.shade, distance);
 
             draw_surface_ray(
-                buffer,
+                band,
                 width,
-                height,
+                rows,
                 env_x as f32,
-                env_y as f32,
-                ray_height,
+                env_y,
+                top,
+                length,
                 max as f32,
                 index,
                 ratio,
+                first,
                 &color_base,
                 &color_shade,
                 orientation,
@@ -252,12 +768,105 @@ pub fn draw_rays(state: &mut State, buffer: &mut [u8], width: u32, height: u32,
     }
 }
 
-// Generate and render map rays
+// Render map rays
 pub fn draw_map_rays(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
     draw_rays(state, buffer, width, height, Type::Map);
 }
 
-// Generate and render scene rays
+// Render scene rays
 pub fn draw_surface_rays(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
     draw_rays(state, buffer, width, height, Type::Surface);
 }
+
+// Generate and render sprites, clipped per column against the depth buffer
+pub fn draw_sprites(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
+    let Configuration {
+        fov,
+        ratio,
+        ref fog,
+        ..
+    } = state.conf;
+
+    let Environment {
+        x: env_x,
+        y: env_y,
+        width: env_width,
+        height: max,
+        unit,
+        ..
+    } = state.env;
+
+    let Subject {
+        x: sub_x,
+        y: sub_y,
+        direction,
+        ..
+    } = state.sub;
+
+    let max = max as f32;
+
+    let shift = shear(state);
+    let eye = state.sub.eye_height();
+
+    let mut order: Vec<(f32, usize)> = state
+        .sprites
+        .iter()
+        .enumerate()
+        .map(|(index, sprite)| (magnitude(sub_x, sub_y, sprite.x, sprite.y), index))
+        .collect();
+
+    order.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    for (distance, index) in order {
+        let sprite = &state.sprites[index];
+
+        let Some(texture) = state.textures.sprites.get(sprite.texture) else {
+            continue;
+        };
+
+        let angle = (sprite.y - sub_y).atan2(sprite.x - sub_x);
+        let relative = (angle - direction + PI).rem_euclid(PI * 2.0) - PI;
+        let depth = distance * relative.cos();
+
+        // Angular half-width of the sprite, scale units high and as wide as its texture aspect
+        let aspect = texture.width as f32 / texture.height.max(1) as f32;
+        let half = (unit * sprite.scale * aspect / 2.0).atan2(distance);
+
+        if depth < unit / 10.0 || relative.abs() > fov / 2.0 + half {
+            continue;
+        }
+
+        let wall = max / (depth / unit);
+        let size = wall * sprite.scale;
+        let span = size * aspect;
+
+        let center = angle_column(state, relative) * ratio as f32;
+        let left = center - span / 2.0;
+        let top = env_y as f32 + (max + wall) / 2.0 + shift + wall * (eye - 0.5) - size;
+
+        let start = left.max(0.0) as u32;
+        let end = (left + span).min(env_width as f32).max(0.0) as u32;
+
+        for column in start..end {
+            let ray = (column / ratio) as usize;
+
+            if state
+                .frame
+                .depth
+                .get(ray)
+                .is_some_and(|value| *value < depth)
+            {
+                continue;
+            }
+
+            let u = (column as f32 + 0.5 - left) / span;
+            let x = (env_x + column) as i32;
+
+            let filter = |color: Color| fog.apply(color, distance);
+
+            canvas::texture::column(
+                buffer, width, height, x, top, size, texture, u, 1.0, 0, &filter,
+            );
+        }
+    }
+}
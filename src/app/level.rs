@@ -0,0 +1,1187 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::f32::consts::PI;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use image::ImageError;
+use serde::Deserialize;
+
+use crate::graphics::color::Color;
+use crate::graphics::line::Line;
+
+use super::data::{color, grid, ColorGroup, TILES};
+
+use super::state::{Fog, FogMode, Segment};
+
+// Level loading error
+#[derive(Debug)]
+pub enum LevelError {
+    Io(io::Error),
+    Texture(PathBuf, ImageError),
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+impl LevelError {
+    fn syntax(line: usize, column: usize, message: impl Into<String>) -> Self {
+        LevelError::Syntax {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelError::Io(error) => write!(f, "{error}"),
+            LevelError::Texture(path, error) => write!(f, "{}: {error}", path.display()),
+            LevelError::Syntax {
+                line,
+                column,
+                message,
+            } => write!(f, "{line}:{column}: {message}"),
+        }
+    }
+}
+
+impl Error for LevelError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LevelError::Io(error) => Some(error),
+            LevelError::Texture(_, error) => Some(error),
+            LevelError::Syntax { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for LevelError {
+    fn from(error: io::Error) -> Self {
+        LevelError::Io(error)
+    }
+}
+
+// Player spawn in tile units, direction in radians
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Spawn {
+    pub x: f32,
+    pub y: f32,
+    pub direction: f32,
+}
+
+// Sprite placement in tile units
+#[derive(Debug, Clone, Default)]
+pub struct Placement {
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+    pub texture: PathBuf,
+}
+
+// Level data
+#[derive(Debug, Clone, Default)]
+pub struct Level {
+    pub width: usize,                    // Grid columns
+    pub height: usize,                   // Grid rows
+    pub unit: f32,                       // Map unit size
+    pub spawn: Spawn,                    // Player spawn
+    pub palette: Vec<(u32, ColorGroup)>, // Tile color overrides
+    pub textures: Vec<(u32, PathBuf)>,   // Tile wall textures
+    pub heights: Vec<(u32, f32)>,        // Tile wall heights in units
+    pub grid: Vec<Vec<u32>>,             // Map grid data
+    pub floor: Vec<Vec<u32>>,            // Floor grid data
+    pub ceiling: Vec<Vec<u32>>,          // Ceiling grid data
+    pub sprites: Vec<Placement>,         // Sprite placements
+    pub doors: Vec<u32>,                 // Door tile ids
+    pub segments: Vec<Segment>,          // Wall segments in tile units
+    pub fog: Fog,                        // Fog and distance shading
+}
+
+// Value with source position
+#[derive(Debug, Clone)]
+struct Located<T> {
+    line: usize,
+    column: usize,
+    value: T,
+}
+
+// Grid row with source position
+type Row = Located<String>;
+
+impl Level {
+    pub fn new() -> Self {
+        let grid = grid();
+
+        Self {
+            width: grid[0].len(),
+            height: grid.len(),
+            unit: 10.0,
+            spawn: Spawn {
+                x: 12.0,
+                y: 4.0,
+                direction: PI / 6.0,
+            },
+            palette: Vec::new(),
+            textures: Vec::new(),
+            heights: Vec::new(),
+            grid,
+            floor: Vec::new(),
+            ceiling: Vec::new(),
+            sprites: Vec::new(),
+            doors: Vec::new(),
+            segments: Vec::new(),
+            fog: Fog::new(),
+        }
+    }
+
+    // Load level file, JSON by `.json` extension and ASCII otherwise
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LevelError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)?;
+
+        let json = path
+            .extension()
+            .is_some_and(|extension| extension == "json");
+
+        let mut level = if json {
+            Self::parse_json(&source)?
+        } else {
+            Self::parse(&source)?
+        };
+
+        let directory = path.parent().unwrap_or(Path::new(""));
+
+        for (_, texture) in level.textures.iter_mut() {
+            *texture = directory.join(&texture);
+        }
+
+        for placement in level.sprites.iter_mut() {
+            placement.texture = directory.join(&placement.texture);
+        }
+
+        Ok(level)
+    }
+
+    // Parse ASCII level
+    pub fn parse(source: &str) -> Result<Self, LevelError> {
+        let mut size = None;
+        let mut unit = None;
+        let mut spawn = None;
+        let mut palette = Vec::new();
+        let mut textures = Vec::new();
+        let mut heights = Vec::new();
+        let mut sprites = Vec::new();
+        let mut doors = Vec::new();
+        let mut segments = Vec::new();
+        let mut fog = Fog::new();
+        let mut rows: Option<Vec<Row>> = None;
+        let mut floor: Option<Vec<Row>> = None;
+        let mut ceiling: Option<Vec<Row>> = None;
+
+        let mut lines = source.lines().enumerate().peekable();
+        let mut end = 1;
+
+        while let Some((index, text)) = lines.next() {
+            let line = index + 1;
+            end = line + 1;
+
+            let tokens = tokenize(text);
+
+            let Some(&(column, keyword)) = tokens.first() else {
+                continue;
+            };
+
+            if keyword.starts_with('#') {
+                continue;
+            }
+
+            let arguments = &tokens[1..];
+
+            match keyword {
+                "size" => {
+                    let [width, height] = arguments_n(line, column, keyword, arguments)?;
+                    let width = number::<usize>(line, width)?;
+                    let height = number::<usize>(line, height)?;
+
+                    if width == 0 || height == 0 {
+                        return Err(LevelError::syntax(line, column, "size must not be empty"));
+                    }
+
+                    size = Some(Located {
+                        line,
+                        column,
+                        value: (width, height),
+                    });
+                }
+                "unit" => {
+                    let [value] = arguments_n(line, column, keyword, arguments)?;
+                    let value = float(line, value)?;
+
+                    if value <= 0.0 {
+                        return Err(LevelError::syntax(line, column, "unit must be positive"));
+                    }
+
+                    unit = Some(value);
+                }
+                "spawn" => {
+                    let [x, y, direction] = arguments_n(line, column, keyword, arguments)?;
+
+                    spawn = Some(Located {
+                        line,
+                        column,
+                        value: Spawn {
+                            x: float(line, x)?,
+                            y: float(line, y)?,
+                            direction: float(line, direction)?.to_radians(),
+                        },
+                    });
+                }
+                "color" => {
+                    let Some((&id, colors)) = arguments.split_first() else {
+                        return Err(LevelError::syntax(line, column, "color expects a tile id"));
+                    };
+
+                    let id = tile_id(line, id)?;
+                    let colors = colors
+                        .iter()
+                        .map(|&(column, text)| {
+                            hex(text).ok_or_else(|| {
+                                LevelError::syntax(line, column, format!("invalid color `{text}`"))
+                            })
+                        })
+                        .collect::<Result<Vec<Color>, LevelError>>()?;
+
+                    palette.push((id, group(line, column, &colors)?));
+                }
+                "texture" => {
+                    let [id, (_, path)] = arguments_n(line, column, keyword, arguments)?;
+                    textures.push((tile_id(line, id)?, PathBuf::from(path)));
+                }
+                "height" => {
+                    let [id, value] = arguments_n(line, column, keyword, arguments)?;
+                    let id = tile_id(line, id)?;
+                    let value = float(line, value)?;
+
+                    if value <= 0.0 {
+                        return Err(LevelError::syntax(line, column, "height must be positive"));
+                    }
+
+                    heights.push((id, value));
+                }
+                "fog" => {
+                    let Some((&(mode_column, mode), arguments)) = arguments.split_first() else {
+                        return Err(LevelError::syntax(line, column, "fog expects a mode"));
+                    };
+
+                    let (color, values) = match (mode, arguments) {
+                        ("linear", [color, start, end]) => (color, vec![start, end]),
+                        ("exponential", [color, density]) => (color, vec![density]),
+                        ("linear" | "exponential", _) => {
+                            return Err(LevelError::syntax(
+                                line,
+                                column,
+                                format!("invalid fog arguments for mode `{mode}`"),
+                            ));
+                        }
+                        _ => {
+                            return Err(LevelError::syntax(
+                                line,
+                                mode_column,
+                                format!("unknown fog mode `{mode}`"),
+                            ));
+                        }
+                    };
+
+                    fog.color = hex(color.1).ok_or_else(|| {
+                        LevelError::syntax(line, color.0, format!("invalid color `{}`", color.1))
+                    })?;
+
+                    let columns: Vec<usize> = values.iter().map(|value| value.0).collect();
+
+                    let values = values
+                        .into_iter()
+                        .map(|value| float(line, *value))
+                        .collect::<Result<Vec<f32>, LevelError>>()?;
+
+                    if let Some(index) = values.iter().position(|value| *value < 0.0) {
+                        return Err(LevelError::syntax(
+                            line,
+                            columns[index],
+                            "fog distances and density must not be negative",
+                        ));
+                    }
+
+                    if mode == "linear" && values[0] >= values[1] {
+                        return Err(LevelError::syntax(
+                            line,
+                            columns[1],
+                            "fog end must be greater than start",
+                        ));
+                    }
+
+                    if mode == "linear" {
+                        fog.mode = FogMode::Linear;
+                        fog.start = values[0];
+                        fog.end = values[1];
+                    } else {
+                        fog.mode = FogMode::Exponential;
+                        fog.density = values[0];
+                    }
+                }
+                "door" => {
+                    let [id] = arguments_n(line, column, keyword, arguments)?;
+                    let id = tile_id(line, id)?;
+
+                    if id == 0 {
+                        return Err(LevelError::syntax(line, column, "door tile must not be 0"));
+                    }
+
+                    doors.push(id);
+                }
+                "attenuation" => {
+                    let [value] = arguments_n(line, column, keyword, arguments)?;
+                    fog.attenuation = float(line, value)?;
+
+                    if fog.attenuation < 0.0 {
+                        return Err(LevelError::syntax(
+                            line,
+                            value.0,
+                            "attenuation must not be negative",
+                        ));
+                    }
+                }
+                "sprite" => {
+                    let (x, y, path, scale) = match arguments {
+                        [x, y, (_, path)] => (x, y, path, None),
+                        [x, y, (_, path), scale] => (x, y, path, Some(scale)),
+                        _ => {
+                            return Err(LevelError::syntax(
+                                line,
+                                column,
+                                format!(
+                                    "sprite expects 3 or 4 arguments, found {}",
+                                    arguments.len()
+                                ),
+                            ));
+                        }
+                    };
+
+                    sprites.push(Located {
+                        line,
+                        column,
+                        value: Placement {
+                            x: float(line, *x)?,
+                            y: float(line, *y)?,
+                            scale: scale.map_or(Ok(1.0), |scale| float(line, *scale))?,
+                            texture: PathBuf::from(path),
+                        },
+                    });
+                }
+                "wall" => {
+                    let [x0, y0, x1, y1, id] = arguments_n(line, column, keyword, arguments)?;
+                    let line_segment = Line::new(
+                        float(line, x0)?,
+                        float(line, y0)?,
+                        float(line, x1)?,
+                        float(line, y1)?,
+                    );
+
+                    segments.push(Located {
+                        line,
+                        column,
+                        value: vec![Segment::new(line_segment, tile_id(line, id)?)],
+                    });
+                }
+                "pillar" => {
+                    let [x, y, size, id] = arguments_n(line, column, keyword, arguments)?;
+                    let x = float(line, x)?;
+                    let y = float(line, y)?;
+                    let size = float(line, size)?;
+
+                    segments.push(Located {
+                        line,
+                        column,
+                        value: pillar(x, y, size, tile_id(line, id)?),
+                    });
+                }
+                "grid" | "floor" | "ceiling" => {
+                    if let Some(&(column, _)) = arguments.first() {
+                        return Err(LevelError::syntax(
+                            line,
+                            column,
+                            format!("{keyword} expects no arguments"),
+                        ));
+                    }
+
+                    let target = match keyword {
+                        "grid" => &mut rows,
+                        "floor" => &mut floor,
+                        _ => &mut ceiling,
+                    };
+
+                    if target.is_some() {
+                        return Err(LevelError::syntax(
+                            line,
+                            column,
+                            format!("duplicate {keyword}"),
+                        ));
+                    }
+
+                    let mut block = Vec::new();
+
+                    while let Some((index, text)) =
+                        lines.next_if(|(_, text)| !text.trim().is_empty())
+                    {
+                        let trimmed = text.trim_start();
+                        let offset = text.chars().count() - trimmed.chars().count();
+
+                        block.push(Located {
+                            line: index + 1,
+                            column: offset + 1,
+                            value: trimmed.trim_end().into(),
+                        });
+
+                        end = index + 2;
+                    }
+
+                    *target = Some(block);
+                }
+                _ => {
+                    return Err(LevelError::syntax(
+                        line,
+                        column,
+                        format!("unknown directive `{keyword}`"),
+                    ));
+                }
+            }
+        }
+
+        let size = size.ok_or_else(|| LevelError::syntax(end, 1, "missing size"))?;
+        let spawn = spawn.ok_or_else(|| LevelError::syntax(end, 1, "missing spawn"))?;
+        let rows = rows.ok_or_else(|| LevelError::syntax(end, 1, "missing grid"))?;
+
+        let mut level = build(size, unit.unwrap_or(10.0), spawn, palette, rows, end)?;
+        level.textures = textures;
+        level.heights = heights;
+        level.doors = doors;
+        level.fog = fog;
+        level.sprites = placements(sprites, level.width, level.height)?;
+        level.segments = walls(segments, level.width, level.height)?;
+
+        if let Some(rows) = floor {
+            level.floor = layer(&rows, level.width, level.height, "floor", end)?;
+        }
+
+        if let Some(rows) = ceiling {
+            level.ceiling = layer(&rows, level.width, level.height, "ceiling", end)?;
+        }
+
+        Ok(level)
+    }
+
+    // Parse JSON level
+    pub fn parse_json(source: &str) -> Result<Self, LevelError> {
+        let file: LevelFile = serde_json::from_str(source)
+            .map_err(|error| LevelError::syntax(error.line(), error.column(), error.to_string()))?;
+
+        let (line, column) = locate(source, 0, "\"size\"").unwrap_or((1, 1));
+
+        let [width, height] = file.size;
+
+        if width == 0 || height == 0 {
+            return Err(LevelError::syntax(line, column, "size must not be empty"));
+        }
+
+        let size = Located {
+            line,
+            column,
+            value: (width, height),
+        };
+
+        if !file.unit.is_finite() || file.unit <= 0.0 {
+            let (line, column) = locate(source, 0, "\"unit\"").unwrap_or((1, 1));
+            return Err(LevelError::syntax(
+                line,
+                column,
+                "unit must be finite and positive",
+            ));
+        }
+
+        let (line, column) = locate(source, 0, "\"spawn\"").unwrap_or((1, 1));
+
+        let spawn = Located {
+            line,
+            column,
+            value: Spawn {
+                x: file.spawn.x,
+                y: file.spawn.y,
+                direction: file.spawn.direction.to_radians(),
+            },
+        };
+
+        let start = source.find("\"palette\"").unwrap_or(0);
+        let mut palette = Vec::new();
+
+        for (id, colors) in file.palette.iter() {
+            let (line, column) = locate(source, start, &format!("\"{id}\"")).unwrap_or((1, 1));
+
+            if *id as usize >= TILES {
+                return Err(LevelError::syntax(
+                    line,
+                    column,
+                    format!("unknown tile id {id}"),
+                ));
+            }
+
+            let colors = colors
+                .iter()
+                .map(|text| {
+                    hex(text).ok_or_else(|| {
+                        let (line, column) =
+                            locate(source, start, &format!("\"{text}\"")).unwrap_or((line, column));
+                        LevelError::syntax(line, column + 1, format!("invalid color `{text}`"))
+                    })
+                })
+                .collect::<Result<Vec<Color>, LevelError>>()?;
+
+            palette.push((*id, group(line, column, &colors)?));
+        }
+
+        let rows = json_rows(source, "grid", &file.grid);
+        let end = position(source, source.len()).0;
+
+        let mut level = build(size, file.unit, spawn, palette, rows, end)?;
+
+        let sprites = json_objects(source, "sprites", file.sprites.len())
+            .into_iter()
+            .zip(file.sprites.iter())
+            .map(|((line, column), sprite)| Located {
+                line,
+                column,
+                value: Placement {
+                    x: sprite.x,
+                    y: sprite.y,
+                    scale: sprite.scale,
+                    texture: PathBuf::from(&sprite.texture),
+                },
+            })
+            .collect();
+
+        level.sprites = placements(sprites, level.width, level.height)?;
+
+        let mut segments = Vec::new();
+
+        for ((line, column), wall) in json_objects(source, "walls", file.walls.len())
+            .into_iter()
+            .zip(file.walls.iter())
+        {
+            let [x0, y0, x1, y1] = wall.line;
+
+            segments.push(Located {
+                line,
+                column,
+                value: vec![Segment::new(
+                    Line::new(x0, y0, x1, y1),
+                    tile_id(line, (column, &wall.tile.to_string()))?,
+                )],
+            });
+        }
+
+        for ((line, column), entry) in json_objects(source, "pillars", file.pillars.len())
+            .into_iter()
+            .zip(file.pillars.iter())
+        {
+            let id = tile_id(line, (column, &entry.tile.to_string()))?;
+
+            segments.push(Located {
+                line,
+                column,
+                value: pillar(entry.x, entry.y, entry.size, id),
+            });
+        }
+
+        level.segments = walls(segments, level.width, level.height)?;
+        level.fog.attenuation = file.attenuation;
+
+        if !file.attenuation.is_finite() || file.attenuation < 0.0 {
+            let (line, column) = locate(source, 0, "\"attenuation\"").unwrap_or((1, 1));
+            let message = if file.attenuation.is_finite() {
+                "attenuation must not be negative"
+            } else {
+                "attenuation must be finite"
+            };
+            return Err(LevelError::syntax(line, column, message));
+        }
+
+        if let Some(id) = file
+            .doors
+            .iter()
+            .find(|id| **id == 0 || **id as usize >= TILES)
+        {
+            let (line, column) = locate(source, 0, "\"doors\"").unwrap_or((1, 1));
+            return Err(LevelError::syntax(
+                line,
+                column,
+                format!("invalid door tile {id}"),
+            ));
+        }
+
+        level.doors = file.doors;
+
+        if let Some(fog) = file.fog.as_ref() {
+            let (line, column) = locate(source, 0, "\"fog\"").unwrap_or((1, 1));
+
+            level.fog.mode = match fog.mode.as_str() {
+                "linear" => FogMode::Linear,
+                "exponential" => FogMode::Exponential,
+                mode => {
+                    return Err(LevelError::syntax(
+                        line,
+                        column,
+                        format!("unknown fog mode `{mode}`"),
+                    ));
+                }
+            };
+
+            level.fog.color = hex(&fog.color).ok_or_else(|| {
+                LevelError::syntax(line, column, format!("invalid color `{}`", fog.color))
+            })?;
+
+            let offset = source.find("\"fog\"").unwrap_or(0);
+
+            for (key, value) in [
+                ("start", fog.start),
+                ("end", fog.end),
+                ("density", fog.density),
+            ] {
+                if !value.is_finite() || value < 0.0 {
+                    let (line, column) =
+                        locate(source, offset, &format!("\"{key}\"")).unwrap_or((line, column));
+                    let message = if value.is_finite() {
+                        "fog distances and density must not be negative".to_string()
+                    } else {
+                        format!("fog {key} must be finite")
+                    };
+                    return Err(LevelError::syntax(line, column, message));
+                }
+            }
+
+            if level.fog.mode == FogMode::Linear && fog.start >= fog.end {
+                let (line, column) = locate(source, offset, "\"end\"").unwrap_or((line, column));
+                return Err(LevelError::syntax(
+                    line,
+                    column,
+                    "fog end must be greater than start",
+                ));
+            }
+
+            level.fog.start = fog.start;
+            level.fog.end = fog.end;
+            level.fog.density = fog.density;
+        }
+
+        if let Some(texts) = file.floor.as_ref() {
+            let rows = json_rows(source, "floor", texts);
+            level.floor = layer(&rows, level.width, level.height, "floor", end)?;
+        }
+
+        if let Some(texts) = file.ceiling.as_ref() {
+            let rows = json_rows(source, "ceiling", texts);
+            level.ceiling = layer(&rows, level.width, level.height, "ceiling", end)?;
+        }
+
+        let start = source.find("\"textures\"").unwrap_or(0);
+
+        for (id, path) in file.textures.iter() {
+            if *id as usize >= TILES {
+                let (line, column) = locate(source, start, &format!("\"{id}\"")).unwrap_or((1, 1));
+                return Err(LevelError::syntax(
+                    line,
+                    column,
+                    format!("unknown tile id {id}"),
+                ));
+            }
+
+            level.textures.push((*id, PathBuf::from(path)));
+        }
+
+        let start = source.find("\"heights\"").unwrap_or(0);
+
+        for (id, value) in file.heights.iter() {
+            let (line, column) = locate(source, start, &format!("\"{id}\"")).unwrap_or((1, 1));
+
+            if *id as usize >= TILES {
+                return Err(LevelError::syntax(
+                    line,
+                    column,
+                    format!("unknown tile id {id}"),
+                ));
+            }
+
+            if !value.is_finite() {
+                return Err(LevelError::syntax(line, column, "height must be finite"));
+            }
+
+            if *value <= 0.0 {
+                return Err(LevelError::syntax(line, column, "height must be positive"));
+            }
+
+            level.heights.push((*id, *value));
+        }
+
+        Ok(level)
+    }
+}
+
+// JSON level file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LevelFile {
+    size: [usize; 2],
+    #[serde(default = "default_unit")]
+    unit: f32,
+    spawn: SpawnFile,
+    #[serde(default)]
+    palette: BTreeMap<u32, Vec<String>>,
+    #[serde(default)]
+    textures: BTreeMap<u32, String>,
+    #[serde(default)]
+    heights: BTreeMap<u32, f32>,
+    grid: Vec<String>,
+    floor: Option<Vec<String>>,
+    ceiling: Option<Vec<String>>,
+    #[serde(default)]
+    sprites: Vec<SpriteFile>,
+    #[serde(default)]
+    doors: Vec<u32>,
+    #[serde(default)]
+    walls: Vec<WallFile>,
+    #[serde(default)]
+    pillars: Vec<PillarFile>,
+    fog: Option<FogFile>,
+    #[serde(default)]
+    attenuation: f32,
+}
+
+// JSON fog
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FogFile {
+    mode: String,
+    color: String,
+    #[serde(default)]
+    start: f32,
+    #[serde(default)]
+    end: f32,
+    #[serde(default)]
+    density: f32,
+}
+
+// JSON wall segment, end points in tiles
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WallFile {
+    line: [f32; 4],
+    tile: u32,
+}
+
+// JSON square pillar, center and size in tiles
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PillarFile {
+    x: f32,
+    y: f32,
+    size: f32,
+    tile: u32,
+}
+
+// JSON sprite placement
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpriteFile {
+    x: f32,
+    y: f32,
+    texture: String,
+    #[serde(default = "default_scale")]
+    scale: f32,
+}
+
+// JSON spawn, direction in degrees
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpawnFile {
+    x: f32,
+    y: f32,
+    direction: f32,
+}
+
+fn default_unit() -> f32 {
+    10.0
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+// Build the four sides of a square pillar centered on x and y
+fn pillar(x: f32, y: f32, size: f32, id: u32) -> Vec<Segment> {
+    let half = size / 2.0;
+    let (left, top, right, bottom) = (x - half, y - half, x + half, y + half);
+
+    vec![
+        Segment::new(Line::new(left, top, right, top), id),
+        Segment::new(Line::new(right, top, right, bottom), id),
+        Segment::new(Line::new(right, bottom, left, bottom), id),
+        Segment::new(Line::new(left, bottom, left, top), id),
+    ]
+}
+
+// Validate wall segments, end points may lie on the grid border
+fn walls(
+    segments: Vec<Located<Vec<Segment>>>,
+    width: usize,
+    height: usize,
+) -> Result<Vec<Segment>, LevelError> {
+    let inside = |x: f32, y: f32| x >= 0.0 && y >= 0.0 && x <= width as f32 && y <= height as f32;
+
+    let mut walls = Vec::new();
+
+    for located in segments {
+        for segment in located.value {
+            let Line { x0, y0, x1, y1 } = segment.line;
+
+            if segment.id == 0 {
+                return Err(LevelError::syntax(
+                    located.line,
+                    located.column,
+                    "wall tile must not be 0",
+                ));
+            }
+
+            if !inside(x0, y0) || !inside(x1, y1) {
+                return Err(LevelError::syntax(
+                    located.line,
+                    located.column,
+                    "wall must be inside the grid",
+                ));
+            }
+
+            if segment.line.length() <= 0.0 {
+                return Err(LevelError::syntax(
+                    located.line,
+                    located.column,
+                    "wall must not be empty",
+                ));
+            }
+
+            walls.push(segment);
+        }
+    }
+
+    Ok(walls)
+}
+
+// Find positions of the objects in a JSON array
+fn json_objects(source: &str, key: &str, count: usize) -> Vec<(usize, usize)> {
+    let mut offset = source.find(&format!("\"{key}\"")).unwrap_or(0);
+    let mut positions = Vec::new();
+
+    for _ in 0..count {
+        let found = source[offset..]
+            .find('{')
+            .map_or(offset, |found| offset + found);
+
+        positions.push(position(source, found));
+        offset = found + 1;
+    }
+
+    positions
+}
+
+// Validate sprite placements inside the grid
+fn placements(
+    sprites: Vec<Located<Placement>>,
+    width: usize,
+    height: usize,
+) -> Result<Vec<Placement>, LevelError> {
+    sprites
+        .into_iter()
+        .map(|sprite| {
+            let Placement { x, y, scale, .. } = sprite.value;
+
+            if !(x >= 0.0 && y >= 0.0 && x < width as f32 && y < height as f32) {
+                return Err(LevelError::syntax(
+                    sprite.line,
+                    sprite.column,
+                    "sprite must be inside the grid",
+                ));
+            }
+
+            if scale <= 0.0 {
+                return Err(LevelError::syntax(
+                    sprite.line,
+                    sprite.column,
+                    "sprite scale must be positive",
+                ));
+            }
+
+            Ok(sprite.value)
+        })
+        .collect()
+}
+
+// Locate JSON grid row strings after their key
+fn json_rows(source: &str, key: &str, texts: &[String]) -> Vec<Row> {
+    let mut offset = source.find(&format!("\"{key}\"")).unwrap_or(0);
+    let mut rows = Vec::new();
+
+    for text in texts.iter() {
+        let quoted = format!("\"{text}\"");
+        let found = source[offset..].find(&quoted).map(|found| offset + found);
+        let (line, column) = found.map_or((1, 1), |found| position(source, found + 1));
+
+        offset = found.map_or(offset, |found| found + quoted.len());
+
+        rows.push(Located {
+            line,
+            column,
+            value: text.clone(),
+        });
+    }
+
+    rows
+}
+
+// Validate grid rows and convert them to tile ids
+fn layer(
+    rows: &[Row],
+    width: usize,
+    height: usize,
+    name: &str,
+    end: usize,
+) -> Result<Vec<Vec<u32>>, LevelError> {
+    if rows.len() != height {
+        let (line, column) = rows
+            .get(height)
+            .map_or((end, 1), |row| (row.line, row.column));
+
+        return Err(LevelError::syntax(
+            line,
+            column,
+            format!("expected {height} {name} rows, found {}", rows.len()),
+        ));
+    }
+
+    let mut grid = Vec::with_capacity(height);
+
+    for row in rows.iter() {
+        let mut tiles = Vec::with_capacity(width);
+
+        for (index, character) in row.value.chars().enumerate() {
+            let column = row.column + index;
+
+            if index == width {
+                return Err(LevelError::syntax(
+                    row.line,
+                    column,
+                    format!(
+                        "expected {width} tiles, found {}",
+                        row.value.chars().count()
+                    ),
+                ));
+            }
+
+            let id = tile(character).ok_or_else(|| {
+                LevelError::syntax(row.line, column, format!("invalid tile `{character}`"))
+            })?;
+
+            tiles.push(id);
+        }
+
+        if tiles.len() != width {
+            return Err(LevelError::syntax(
+                row.line,
+                row.column + tiles.len(),
+                format!("expected {width} tiles, found {}", tiles.len()),
+            ));
+        }
+
+        grid.push(tiles);
+    }
+
+    Ok(grid)
+}
+
+// Validate grid and spawn and assemble level
+fn build(
+    size: Located<(usize, usize)>,
+    unit: f32,
+    spawn: Located<Spawn>,
+    palette: Vec<(u32, ColorGroup)>,
+    rows: Vec<Row>,
+    end: usize,
+) -> Result<Level, LevelError> {
+    let (width, height) = size.value;
+
+    let grid = layer(&rows, width, height, "grid", end)?;
+
+    let Spawn { x, y, .. } = spawn.value;
+
+    let inside = x >= 0.0 && y >= 0.0 && x < width as f32 && y < height as f32;
+
+    if !inside || grid[y as usize][x as usize] != 0 {
+        return Err(LevelError::syntax(
+            spawn.line,
+            spawn.column,
+            "spawn must be on an empty tile inside the grid",
+        ));
+    }
+
+    Ok(Level {
+        width,
+        height,
+        unit,
+        spawn: spawn.value,
+        palette,
+        textures: Vec::new(),
+        heights: Vec::new(),
+        grid,
+        floor: Vec::new(),
+        ceiling: Vec::new(),
+        sprites: Vec::new(),
+        doors: Vec::new(),
+        segments: Vec::new(),
+        fog: Fog::new(),
+    })
+}
+
+// Split line into tokens with 1-based columns
+fn tokenize(text: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (index, (offset, character)) in text.char_indices().enumerate() {
+        if character.is_whitespace() {
+            if let Some((first, begin)) = start.take() {
+                tokens.push((first, &text[begin..offset]));
+            }
+        } else if start.is_none() {
+            start = Some((index + 1, offset));
+        }
+    }
+
+    if let Some((first, begin)) = start {
+        tokens.push((first, &text[begin..]));
+    }
+
+    tokens
+}
+
+// Require exact argument count
+fn arguments_n<'a, const N: usize>(
+    line: usize,
+    column: usize,
+    keyword: &str,
+    arguments: &[(usize, &'a str)],
+) -> Result<[(usize, &'a str); N], LevelError> {
+    arguments.try_into().map_err(|_| {
+        LevelError::syntax(
+            line,
+            column,
+            format!("{keyword} expects {N} arguments, found {}", arguments.len()),
+        )
+    })
+}
+
+fn number<T: std::str::FromStr>(
+    line: usize,
+    (column, text): (usize, &str),
+) -> Result<T, LevelError> {
+    text.parse()
+        .map_err(|_| LevelError::syntax(line, column, format!("invalid number `{text}`")))
+}
+
+// Parse a finite floating point number, rejecting `NaN` and infinities
+fn float(line: usize, (column, text): (usize, &str)) -> Result<f32, LevelError> {
+    let value = number::<f32>(line, (column, text))?;
+
+    if !value.is_finite() {
+        return Err(LevelError::syntax(
+            line,
+            column,
+            format!("number `{text}` must be finite"),
+        ));
+    }
+
+    Ok(value)
+}
+
+fn tile_id(line: usize, token: (usize, &str)) -> Result<u32, LevelError> {
+    let id = number::<u32>(line, token)?;
+
+    if id as usize >= TILES {
+        return Err(LevelError::syntax(
+            line,
+            token.0,
+            format!("unknown tile id {id}"),
+        ));
+    }
+
+    Ok(id)
+}
+
+// Map grid character to tile id
+fn tile(character: char) -> Option<u32> {
+    match character {
+        '.' => Some(0),
+        _ => character.to_digit(10).filter(|id| (*id as usize) < TILES),
+    }
+}
+
+// Parse `#rrggbb` color
+fn hex(text: &str) -> Option<Color> {
+    let digits = text.strip_prefix('#')?;
+
+    if digits.len() != 6 || !digits.chars().all(|digit| digit.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16).ok();
+
+    Some(color(channel(0)?, channel(2)?, channel(4)?, 255))
+}
+
+// Build color group from base or base, tint and shade
+fn group(line: usize, column: usize, colors: &[Color]) -> Result<ColorGroup, LevelError> {
+    match colors {
+        [base] => Ok(ColorGroup::from_base(*base)),
+        [base, tint, shade] => Ok(ColorGroup::new(*base, *tint, *shade)),
+        _ => Err(LevelError::syntax(
+            line,
+            column,
+            format!("color expects 1 or 3 colors, found {}", colors.len()),
+        )),
+    }
+}
+
+// Compute 1-based line and column of byte offset
+fn position(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let start = before.rfind('\n').map_or(0, |index| index + 1);
+    let column = before[start..].chars().count() + 1;
+    (line, column)
+}
+
+// Find text after byte offset and return its position
+fn locate(source: &str, offset: usize, text: &str) -> Option<(usize, usize)> {
+    let found = source[offset..].find(text)?;
+    Some(position(source, offset + found))
+}
@@ -1,26 +1,44 @@
-use super::detection::{collision, constrain};
-use super::graphics::{draw_map, draw_map_rays, draw_subject, draw_surface, draw_surface_rays};
-use super::state::{Configuration, Environment, Map, State, Subject};
+use crate::graphics::point::Point;
+use crate::input::controls::Action;
 
-// Handle keyboard input
-pub fn handle_input(state: &mut State) {
-    state.sub.rotate_direction = 0.0;
-    state.sub.translate_direction = 0.0;
+use super::data::{CROUCH, EYE, PITCH, REACH};
+use super::detection::{constrain, intersection, magnitude, slide};
+use super::graphics::{
+    cast_rays, draw_map, draw_map_rays, draw_overlay, draw_sprites, draw_subject, draw_surface,
+    draw_surface_rays,
+};
+use super::state::{Configuration, Environment, Frame, Map, State, Subject};
 
-    if state.conf.left && !state.conf.right {
-        state.sub.rotate_direction = -1.0;
-    }
+// Get axis value from a pair of opposing actions
+fn axis(conf: &Configuration, negative: Action, positive: Action) -> f32 {
+    conf.value(positive) - conf.value(negative)
+}
 
-    if state.conf.right && !state.conf.left {
-        state.sub.rotate_direction = 1.0;
-    }
+// Handle action input
+pub fn handle_input(state: &mut State) {
+    state.sub.rotate_direction = axis(&state.conf, Action::TurnLeft, Action::TurnRight);
+    state.sub.translate_direction = axis(&state.conf, Action::Backward, Action::Forward);
+    state.sub.strafe_direction = axis(&state.conf, Action::StrafeLeft, Action::StrafeRight);
+    state.sub.pitch_direction = axis(&state.conf, Action::LookDown, Action::LookUp);
+}
 
-    if state.conf.up && !state.conf.down {
-        state.sub.translate_direction = 1.0;
+// Move value towards target by at most step
+fn approach(value: f32, target: f32, step: f32) -> f32 {
+    if value < target {
+        (value + step).min(target)
+    } else {
+        (value - step).max(target)
     }
+}
 
-    if state.conf.down && !state.conf.up {
-        state.sub.translate_direction = -1.0;
+// Move velocity towards target, instantly when acceleration or friction is 0
+fn accelerate(velocity: f32, target: f32, acceleration: f32, friction: f32, delta: f32) -> f32 {
+    if target != 0.0 && acceleration > 0.0 {
+        approach(velocity, target, acceleration * delta)
+    } else if target == 0.0 && friction > 0.0 {
+        approach(velocity, target, friction * delta)
+    } else {
+        target
     }
 }
 
@@ -29,10 +47,16 @@ pub fn update_state(state: &mut State, time: f32, delta: f32, fps: f32) {
     state.conf.time = time;
     state.conf.delta = delta;
     state.conf.fps = fps;
+    state.message.time = (state.message.time - delta).max(0.0);
 
     handle_input(state);
 
-    let Configuration { fov, ref grid, .. } = state.conf;
+    let Configuration {
+        fov,
+        ref grid,
+        look,
+        ..
+    } = state.conf;
 
     let Environment { unit, map, .. } = state.env;
 
@@ -41,28 +65,192 @@ pub fn update_state(state: &mut State, time: f32, delta: f32, fps: f32) {
     let Subject {
         x,
         y,
+        radius,
         direction,
-        translate_amount,
+        rotate_speed,
+        translate_speed,
+        acceleration,
+        friction,
+        velocity,
+        strafe_velocity,
         translate_direction,
-        rotate_amount,
         rotate_direction,
+        strafe_direction,
         ..
     } = state.sub;
 
-    let rotation = rotate_direction * rotate_amount;
-    let translation = translate_direction * translate_amount;
+    // Keep diagonal movement at translate speed
+    let scale = 1.0 / translate_direction.hypot(strafe_direction).max(1.0);
+
+    let velocity = accelerate(
+        velocity,
+        translate_direction * translate_speed * scale,
+        acceleration,
+        friction,
+        delta,
+    );
+
+    let strafe_velocity = accelerate(
+        strafe_velocity,
+        strafe_direction * translate_speed * scale,
+        acceleration,
+        friction,
+        delta,
+    );
+
+    state.sub.velocity = velocity;
+    state.sub.strafe_velocity = strafe_velocity;
+
+    let rotation = rotate_direction * rotate_speed * delta + look;
+    let translation = velocity * delta;
+    let strafe = strafe_velocity * delta;
+
+    state.conf.look = 0.0;
+
+    let pitch = state.sub.pitch + state.sub.pitch_direction * state.sub.pitch_speed * delta;
+
+    state.sub.pitch = (pitch + state.conf.pitch).clamp(-PITCH, PITCH);
+    state.conf.pitch = 0.0;
+
+    // Cast the frame around the rotated heading
+    let heading = constrain(direction + rotation);
 
-    state.sub.direction = constrain(direction + rotation);
-    state.sub.sector = constrain(direction - fov / 2.0);
+    state.sub.direction = heading;
+    state.sub.sector = constrain(heading - fov / 2.0);
+    state.sub.dir = Point::new(heading.cos(), heading.sin());
+    state.sub.plane = Point::new(-heading.sin(), heading.cos()).scale((fov / 2.0).tan());
 
-    let x = x + translation * direction.cos();
-    let y = y + translation * direction.sin();
+    let delta_x = translation * direction.cos() - strafe * direction.sin();
+    let delta_y = translation * direction.sin() + strafe * direction.cos();
 
-    let id = collision(x, y, width, height, unit, grid);
+    let doors = &state.doors;
+    let segments = &state.segments;
 
-    if id == 0 {
-        state.sub.x = x;
-        state.sub.y = y;
+    let (x, y) = slide(
+        x, y, delta_x, delta_y, radius, width, height, unit, grid, doors, segments,
+    );
+
+    state.sub.x = x;
+    state.sub.y = y;
+
+    let using = state.conf.value(Action::Use) > 0.5;
+
+    if using && !state.conf.using {
+        use_door(state);
+    }
+
+    state.conf.using = using;
+
+    update_doors(state, delta);
+    update_height(state, delta);
+    update_frame(state);
+}
+
+// Cast the rays of every surface column once for all render passes
+pub fn update_frame(state: &mut State) {
+    let rays = cast_rays(state);
+    let direction = state.sub.direction;
+
+    let depth = rays
+        .iter()
+        .map(|rays| {
+            rays.first().map_or(f32::INFINITY, |nearest| {
+                nearest.distance * (direction - nearest.angle).cos()
+            })
+        })
+        .collect();
+
+    state.frame = Frame { rays, depth };
+}
+
+// Crouch towards the crouching eye height and jump from the floor
+pub fn update_height(state: &mut State, delta: f32) {
+    let crouching = state.conf.value(Action::Crouch) > 0.5;
+    let jumping = state.conf.value(Action::Jump) > 0.5;
+
+    let sub = &mut state.sub;
+
+    let target = if crouching { CROUCH } else { EYE };
+    sub.eye = approach(sub.eye, target, sub.crouch_speed * delta);
+
+    if jumping && sub.elevation <= 0.0 {
+        sub.vertical_velocity = sub.jump_speed;
+    }
+
+    if sub.elevation > 0.0 || sub.vertical_velocity > 0.0 {
+        sub.vertical_velocity -= sub.gravity * delta;
+        sub.elevation += sub.vertical_velocity * delta;
+    }
+
+    if sub.elevation <= 0.0 {
+        sub.elevation = 0.0;
+        sub.vertical_velocity = 0.0;
+    }
+}
+
+// Toggle the nearest door in front of the subject within reach
+pub fn use_door(state: &mut State) {
+    let Environment { unit, map, .. } = state.env;
+
+    let Subject {
+        x, y, direction, ..
+    } = state.sub;
+
+    let width = (map.width / unit).floor() as i32;
+    let height = (map.height / unit).floor() as i32;
+
+    // Sample points along the view direction every quarter tile
+    let steps = (REACH * 4.0) as u32;
+
+    for step in 0..=steps {
+        let distance = unit * REACH * step as f32 / steps as f32;
+
+        let cell_x = ((x + direction.cos() * distance) / unit).floor() as i32;
+        let cell_y = ((y + direction.sin() * distance) / unit).floor() as i32;
+
+        if let Some(door) = state
+            .doors
+            .iter_mut()
+            .find(|door| door.x == cell_x && door.y == cell_y)
+        {
+            let open = door.direction > 0.0 || (door.direction == 0.0 && door.open >= 1.0);
+            door.direction = if open { -1.0 } else { 1.0 };
+
+            return;
+        }
+
+        if intersection(cell_x, cell_y, width, height, &state.conf.grid) != 0 {
+            return;
+        }
+    }
+}
+
+// Slide doors open or closed, keeping doors open while the subject stands in them
+pub fn update_doors(state: &mut State, delta: f32) {
+    let unit = state.env.unit;
+
+    let Subject { x, y, radius, .. } = state.sub;
+
+    let speed = state.conf.door_speed;
+
+    for door in state.doors.iter_mut() {
+        if door.direction < 0.0 {
+            let left = door.x as f32 * unit;
+            let top = door.y as f32 * unit;
+
+            let near_x = x.clamp(left, left + unit);
+            let near_y = y.clamp(top, top + unit);
+
+            if magnitude(x, y, near_x, near_y) < radius {
+                continue;
+            }
+        }
+
+        door.open = (door.open + door.direction * speed * delta).clamp(0.0, 1.0);
+
+        if door.open <= 0.0 || door.open >= 1.0 {
+            door.direction = 0.0;
+        }
     }
 }
 
@@ -70,7 +258,12 @@ pub fn update_state(state: &mut State, time: f32, delta: f32, fps: f32) {
 pub fn render_graphics(state: &mut State, buffer: &mut [u8], width: u32, height: u32) {
     draw_surface(state, buffer, width, height);
     draw_surface_rays(state, buffer, width, height);
+    draw_sprites(state, buffer, width, height);
     draw_map(state, buffer, width, height);
     draw_map_rays(state, buffer, width, height);
     draw_subject(state, buffer, width, height);
+
+    if state.conf.overlay {
+        draw_overlay(state, buffer, width, height);
+    }
 }
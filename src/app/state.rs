@@ -1,14 +1,21 @@
 use std::f32::consts::PI;
 
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::path::Path;
+use std::thread;
 
 use crate::display::window::Graphics;
 
 use crate::graphics::color::Color;
+use crate::graphics::line::Line;
+use crate::graphics::point::Point;
 use crate::graphics::rectangle::Rectangle;
+use crate::graphics::texture::Texture;
+use crate::input::controls::Action;
 
-use super::data::{color_list, grid, ColorGroup};
-use super::data::{GREY2, GREY3, GREY7, GREY8};
+use super::data::{color_list, grid, ColorGroup, EYE, MESSAGE, MINIMAP, TILES};
+use super::data::{BLACK, GREY2, GREY3, GREY4, GREY7, GREY8, GREY9, SMOKE};
+use super::level::{Level, LevelError};
 use super::logic::{render_graphics, update_state};
 
 pub type Map = Rectangle;
@@ -22,8 +29,23 @@ pub enum Type {
     Surface,
 }
 
-// Ray orientation
+// Surface plane
 #[derive(PartialEq)]
+pub enum Plane {
+    Floor,
+    Ceiling,
+}
+
+// Camera projection
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum Projection {
+    #[default]
+    Angular, // Rays spaced by a constant angle, corrected for fisheye
+    CameraPlane, // Rays spaced evenly along a camera plane in front of the subject
+}
+
+// Ray orientation
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Orientation {
     None,
     Left,
@@ -37,64 +59,206 @@ pub struct Collision {
     pub id: i32,
     pub distance: f32,
     pub orientation: Orientation,
+    pub offset: f32, // Hit offset along tile face
+    pub x: f32,      // Hit point x
+    pub y: f32,      // Hit point y
+    pub cell_x: i32, // Hit tile column
+    pub cell_y: i32, // Hit tile row
+    pub exit: f32,   // Distance where the ray leaves the hit tile, same as distance for thin walls
 }
 
 impl Collision {
-    pub fn new(id: i32, distance: f32, orientation: Orientation) -> Self {
+    pub fn new(
+        id: i32,
+        distance: f32,
+        orientation: Orientation,
+        offset: f32,
+        x: f32,
+        y: f32,
+        cell_x: i32,
+        cell_y: i32,
+    ) -> Self {
         Self {
             id,
             distance,
             orientation,
+            offset,
+            x,
+            y,
+            cell_x,
+            cell_y,
+            exit: distance,
         }
     }
 }
 
 // Ray data
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Ray {
     pub id: i32,
     pub angle: f32,
     pub height: f32,
     pub distance: f32,
     pub orientation: Orientation,
+    pub offset: f32, // Hit offset along tile face
+    pub wall: f32,   // Wall height in units
+    pub exit: f32,   // Projected unit height where the ray leaves the hit tile
 }
 
 impl Ray {
-    pub fn new(id: i32, angle: f32, height: f32, distance: f32, orientation: Orientation) -> Self {
+    pub fn new(
+        id: i32,
+        angle: f32,
+        height: f32,
+        distance: f32,
+        orientation: Orientation,
+        offset: f32,
+        wall: f32,
+        exit: f32,
+    ) -> Self {
         Self {
             id,
             angle,
             height,
             distance,
             orientation,
+            offset,
+            wall,
+            exit,
         }
     }
 }
 
+// Sprite data
+#[derive(Debug, Clone, Default)]
+pub struct Sprite {
+    pub x: f32,         // Sprite x
+    pub y: f32,         // Sprite y
+    pub scale: f32,     // Sprite scale relative to wall height
+    pub texture: usize, // Sprite texture index
+}
+
+impl Sprite {
+    pub fn new(x: f32, y: f32, scale: f32, texture: usize) -> Self {
+        Self {
+            x,
+            y,
+            scale,
+            texture,
+        }
+    }
+}
+
+// Wall segment data
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Segment {
+    pub line: Line, // Segment end points
+    pub id: u32,    // Tile id for color and texture
+}
+
+impl Segment {
+    pub fn new(line: Line, id: u32) -> Self {
+        Self { line, id }
+    }
+}
+
+// Door data
+#[derive(Debug, Clone, Default)]
+pub struct Door {
+    pub x: i32,         // Door tile column
+    pub y: i32,         // Door tile row
+    pub vertical: bool, // Door plane runs along y through the tile center
+    pub open: f32,      // Open amount, from closed (0) to open (1)
+    pub direction: f32, // Slide direction, opening (1), closing (-1) or idle (0)
+}
+
+impl Door {
+    pub fn new(x: i32, y: i32, vertical: bool) -> Self {
+        Self {
+            x,
+            y,
+            vertical,
+            open: 0.0,
+            direction: 0.0,
+        }
+    }
+    pub fn closed(&self) -> bool {
+        self.open < 1.0
+    }
+}
+
 // State setup
 #[derive(Default)]
 pub struct State {
-    pub conf: Configuration, // Game
-    pub env: Environment,    // Surface
-    pub sub: Subject,        // Player
-    pub colors: Colors,      // Colors
+    pub conf: Configuration,    // Game
+    pub env: Environment,       // Surface
+    pub sub: Subject,           // Player
+    pub colors: Colors,         // Colors
+    pub textures: Textures,     // Textures
+    pub sprites: Vec<Sprite>,   // Sprites
+    pub doors: Vec<Door>,       // Doors
+    pub segments: Vec<Segment>, // Wall segments
+    pub frame: Frame,           // Rays cast for the current frame
+    pub message: Message,       // On-screen message
+}
+
+// Frame data cast once per update and read by every render pass
+#[derive(Debug, Clone, Default)]
+pub struct Frame {
+    pub rays: Vec<Vec<Ray>>, // Hits per surface column, nearest first
+    pub depth: Vec<f32>,     // Column depth buffer, perpendicular distance of the nearest hit
+}
+
+// Message shown on screen until its time runs out
+#[derive(Debug, Clone, Default)]
+pub struct Message {
+    pub text: String, // Message text, may span multiple lines
+    pub time: f32,    // Seconds left on screen
 }
 
 // Game setup
 #[derive(Default)]
 pub struct Configuration {
-    pub fov: f32,              // Field of view
-    pub arc: f32,              // Arc delta
-    pub ratio: u32,            // Ray ratio
-    pub resolution: u32,       // Ray resolution
-    pub time: f32,             // Frame total time
-    pub delta: f32,            // Frame delta time
-    pub fps: f32,              // Frames per second
-    pub left: bool,            // Key left
-    pub right: bool,           // Key right
-    pub up: bool,              // Key up
-    pub down: bool,            // Key down
-    pub grid: Vec<Vec<u32>>,   // Map grid data
-    pub keys: HashSet<String>, // Keys pressed
+    pub fov: f32,                      // Field of view
+    pub arc: f32,                      // Arc delta
+    pub ratio: u32,                    // Ray ratio
+    pub resolution: u32,               // Ray resolution
+    pub time: f32,                     // Frame total time
+    pub delta: f32,                    // Frame delta time
+    pub fps: f32,                      // Frames per second
+    pub grid: Vec<Vec<u32>>,           // Map grid data
+    pub floor: Vec<Vec<u32>>,          // Floor grid data
+    pub ceiling: Vec<Vec<u32>>,        // Ceiling grid data
+    pub heights: [f32; TILES],         // Wall heights per tile id in units
+    pub actions: HashMap<Action, f32>, // Action values
+    pub look: f32,                     // Pending look yaw in radians
+    pub pitch: f32,                    // Pending look pitch in radians
+    pub using: bool,                   // Use action held on previous update
+    pub door_speed: f32,               // Door open amount per second
+    pub fog: Fog,                      // Fog and distance shading
+    pub projection: Projection,        // Camera projection
+    pub threads: usize,                // Render threads, 1 renders on the calling thread
+    pub overlay: bool,                 // Draw frame rate, coordinates and messages
+}
+
+// Fog mode
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum FogMode {
+    #[default]
+    None,
+    Linear,
+    Exponential,
+}
+
+// Fog and distance shading setup
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Fog {
+    pub mode: FogMode,    // Fog mode
+    pub color: Color,     // Fog color
+    pub start: f32,       // Linear fog start distance
+    pub end: f32,         // Linear fog end distance
+    pub density: f32,     // Exponential fog density
+    pub attenuation: f32, // Distance light attenuation
 }
 
 // Surface properties
@@ -105,7 +269,8 @@ pub struct Environment {
     pub width: u32,  // Surface width
     pub height: u32, // Surface height
     pub unit: f32,   // Map unit size
-    pub map: Map,    // Map coordinates and dimensions
+    pub map: Map,    // Minimap coordinates and map dimensions in units
+    pub scale: f32,  // Minimap pixels per unit
 }
 
 // Player properties
@@ -116,10 +281,26 @@ pub struct Subject {
     pub radius: f32,              // Player radius
     pub sector: f32,              // Player fov sector start
     pub direction: f32,           // Player direction
-    pub rotate_amount: f32,       // Rotate amount
-    pub translate_amount: f32,    // Translate amount
+    pub dir: Point,               // Player direction vector
+    pub plane: Point,             // Camera plane vector, half the view width at unit distance
+    pub rotate_speed: f32,        // Rotate speed in radians per second
+    pub translate_speed: f32,     // Translate speed in units per second
+    pub acceleration: f32,        // Translate acceleration in units per second², 0 for instant
+    pub friction: f32,            // Translate deceleration in units per second², 0 for instant
+    pub velocity: f32,            // Current translate velocity in units per second
+    pub strafe_velocity: f32,     // Current strafe velocity in units per second
     pub rotate_direction: f32,    // Rotate direction
     pub translate_direction: f32, // Translate direction
+    pub strafe_direction: f32,    // Strafe direction, positive to the right
+    pub pitch: f32,               // Look pitch in radians, positive looks up
+    pub pitch_speed: f32,         // Pitch speed in radians per second
+    pub pitch_direction: f32,     // Pitch direction, positive looks up
+    pub eye: f32,                 // Eye height above the floor in units, lowered while crouching
+    pub crouch_speed: f32,        // Eye height change in units per second
+    pub elevation: f32,           // Jump height above the floor in units
+    pub vertical_velocity: f32,   // Jump velocity in units per second
+    pub jump_speed: f32,          // Initial jump velocity in units per second
+    pub gravity: f32,             // Jump deceleration in units per second²
 }
 
 // Colors
@@ -128,8 +309,44 @@ pub struct Colors {
     pub ceiling: Color,
     pub floor: Color,
     pub player: Color,
+    pub cone: Color,
+    pub text: Color,
+    pub panel: Color,
+    pub sprite: Color,
     pub map: Color,
-    pub list: [ColorGroup; 10],
+    pub list: [ColorGroup; TILES],
+}
+
+// Textures
+#[derive(Default)]
+pub struct Textures {
+    pub walls: HashMap<u32, Texture>,
+    pub sprites: Vec<Texture>,
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Self {
+            rays: Vec::new(),
+            depth: Vec::new(),
+        }
+    }
+    // Nearest hit of a surface column
+    pub fn nearest(&self, index: usize) -> Option<&Ray> {
+        self.rays.get(index).and_then(|rays| rays.first())
+    }
+}
+
+impl Message {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            time: 0.0,
+        }
+    }
+    pub fn visible(&self) -> bool {
+        self.time > 0.0 && !self.text.is_empty()
+    }
 }
 
 impl State {
@@ -139,17 +356,110 @@ impl State {
             env: Environment::new(),
             sub: Subject::new(),
             colors: Colors::new(),
+            textures: Textures::new(),
+            sprites: Vec::new(),
+            doors: Vec::new(),
+            segments: Vec::new(),
+            frame: Frame::new(),
+            message: Message::new(),
         }
     }
     pub fn init(&mut self) {
+        let rows = self.conf.grid.len();
+        let columns = self.conf.grid.first().map_or(0, |row| row.len());
+        self.env.fit(columns, rows);
         self.conf.resolution = self.env.width / self.conf.ratio;
         self.conf.arc = self.conf.fov / self.conf.resolution as f32;
     }
+    // Show a message on screen, replacing the current one
+    pub fn show(&mut self, text: impl Into<String>) {
+        self.message.text = text.into();
+        self.message.time = MESSAGE;
+    }
     pub fn build() -> Self {
         let mut state = Self::new();
         state.init();
         state
     }
+    pub fn apply(&mut self, level: &Level) {
+        self.conf.grid = level.grid.clone();
+        self.conf.floor = level.floor.clone();
+        self.conf.ceiling = level.ceiling.clone();
+        self.conf.fog = level.fog;
+        self.env.unit = level.unit;
+        self.sub.x = level.spawn.x * level.unit;
+        self.sub.y = level.spawn.y * level.unit;
+        self.sub.direction = level.spawn.direction;
+        for (id, group) in level.palette.iter() {
+            self.colors.list[*id as usize] = *group;
+        }
+        for (id, height) in level.heights.iter() {
+            self.conf.heights[*id as usize] = *height;
+        }
+        self.doors = doors(&level.grid, &level.doors);
+        self.segments = level
+            .segments
+            .iter()
+            .map(|segment| {
+                let Line { x0, y0, x1, y1 } = segment.line;
+                let unit = level.unit;
+                Segment::new(
+                    Line::new(x0 * unit, y0 * unit, x1 * unit, y1 * unit),
+                    segment.id,
+                )
+            })
+            .collect();
+    }
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LevelError> {
+        let level = Level::load(path)?;
+        let mut state = Self::new();
+        state.apply(&level);
+        for (id, path) in level.textures.iter() {
+            let texture =
+                Texture::load(path).map_err(|error| LevelError::Texture(path.clone(), error))?;
+            state.textures.walls.insert(*id, texture);
+        }
+        let mut paths: HashMap<&Path, usize> = HashMap::new();
+        for placement in level.sprites.iter() {
+            let path = placement.texture.as_path();
+            let index = match paths.get(path) {
+                Some(index) => *index,
+                None => {
+                    let texture = Texture::load(path)
+                        .map_err(|error| LevelError::Texture(path.into(), error))?;
+                    state.textures.sprites.push(texture);
+                    paths.insert(path, state.textures.sprites.len() - 1);
+                    state.textures.sprites.len() - 1
+                }
+            };
+            let x = placement.x * level.unit;
+            let y = placement.y * level.unit;
+            state
+                .sprites
+                .push(Sprite::new(x, y, placement.scale, index));
+        }
+        state.init();
+        Ok(state)
+    }
+}
+
+// Create doors for door tiles, sliding across the corridor they block
+fn doors(grid: &[Vec<u32>], ids: &[u32]) -> Vec<Door> {
+    let solid = |x: usize, y: usize, dx: isize| {
+        x.checked_add_signed(dx)
+            .and_then(|x| grid[y].get(x))
+            .is_some_and(|id| *id > 0 && !ids.contains(id))
+    };
+    let mut doors = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, id) in row.iter().enumerate() {
+            if ids.contains(id) {
+                let vertical = !(solid(x, y, -1) && solid(x, y, 1));
+                doors.push(Door::new(x as i32, y as i32, vertical));
+            }
+        }
+    }
+    doors
 }
 
 impl Configuration {
@@ -162,28 +472,90 @@ impl Configuration {
             time: 0.0,
             delta: 0.0,
             fps: 0.0,
-            left: false,
-            right: false,
-            up: false,
-            down: false,
             grid: grid(),
-            keys: HashSet::new(),
+            floor: Vec::new(),
+            ceiling: Vec::new(),
+            heights: [1.0; TILES],
+            actions: HashMap::new(),
+            look: 0.0,
+            pitch: 0.0,
+            using: false,
+            door_speed: 1.0,
+            fog: Fog::new(),
+            projection: Projection::Angular,
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            overlay: true,
+        }
+    }
+    // Get current action value, 0 when released
+    pub fn value(&self, action: Action) -> f32 {
+        self.actions.get(&action).copied().unwrap_or(0.0)
+    }
+}
+
+impl Fog {
+    pub fn new() -> Self {
+        Self {
+            mode: FogMode::None,
+            color: BLACK,
+            start: 0.0,
+            end: 0.0,
+            density: 0.0,
+            attenuation: 0.0,
+        }
+    }
+    pub fn active(&self) -> bool {
+        self.mode != FogMode::None || self.attenuation > 0.0
+    }
+    // Fog amount at distance, from clear (0) to fog color (1)
+    pub fn amount(&self, distance: f32) -> f32 {
+        match self.mode {
+            FogMode::None => 0.0,
+            FogMode::Linear => {
+                let range = (self.end - self.start).max(f32::EPSILON);
+                ((distance - self.start) / range).clamp(0.0, 1.0)
+            }
+            FogMode::Exponential => 1.0 - (-self.density * distance).exp(),
+        }
+    }
+    // Attenuate and fog color at distance
+    pub fn apply(&self, color: Color, distance: f32) -> Color {
+        if !self.active() {
+            return color;
         }
+        let light = 1.0 / (1.0 + self.attenuation * distance);
+        color.scale(light).mix(self.color, self.amount(distance))
     }
 }
 
 impl Environment {
     pub fn new() -> Self {
-        let map = Map::new(640.0, 480.0, 320.0, 240.0, true);
         Self {
             x: 0,
             y: 0,
             width: 960,
             height: 720,
             unit: 10.0,
-            map,
+            map: Map::default(),
+            scale: 1.0,
         }
     }
+    // Place map in the bottom right corner of the surface, shrinking it to fit the minimap fraction
+    pub fn fit(&mut self, columns: usize, rows: usize) {
+        let width = columns as f32 * self.unit;
+        let height = rows as f32 * self.unit;
+        let tile = (self.width as f32 * MINIMAP / columns.max(1) as f32)
+            .min(self.height as f32 * MINIMAP / rows.max(1) as f32)
+            .min(self.unit);
+        self.scale = tile / self.unit;
+        let x = (self.x + self.width) as f32 - width * self.scale;
+        let y = (self.y + self.height) as f32 - height * self.scale;
+        self.map = Map::new(x, y, width, height, true);
+    }
+    // Minimap pixel position of a map position in units
+    pub fn minimap(&self, x: f32, y: f32) -> Point {
+        Point::new(self.map.x + x * self.scale, self.map.y + y * self.scale)
+    }
 }
 
 impl Subject {
@@ -194,12 +566,32 @@ impl Subject {
             radius: 2.0,
             sector: 0.0,
             direction: PI / 6.0,
-            rotate_amount: PI * 0.0125,
-            translate_amount: 1.0,
+            dir: Point::new(0.0, 0.0),
+            plane: Point::new(0.0, 0.0),
+            rotate_speed: PI * 0.75,
+            translate_speed: 60.0,
+            acceleration: 0.0,
+            friction: 0.0,
+            velocity: 0.0,
+            strafe_velocity: 0.0,
             rotate_direction: 0.0,
             translate_direction: 0.0,
+            strafe_direction: 0.0,
+            pitch: 0.0,
+            pitch_speed: PI / 2.0,
+            pitch_direction: 0.0,
+            eye: EYE,
+            crouch_speed: 2.0,
+            elevation: 0.0,
+            vertical_velocity: 0.0,
+            jump_speed: 2.0,
+            gravity: 8.0,
         }
     }
+    // Eye height above the floor in units, including jumps
+    pub fn eye_height(&self) -> f32 {
+        self.eye + self.elevation
+    }
 }
 
 impl Colors {
@@ -208,25 +600,33 @@ impl Colors {
             ceiling: GREY3,
             floor: GREY7,
             player: GREY2,
+            cone: SMOKE,
+            text: GREY9,
+            panel: SMOKE,
+            sprite: GREY4,
             map: GREY8,
             list: color_list(),
         }
     }
 }
 
-impl Graphics for State {
-    fn input(&mut self, active: bool, key: &str) {
-        if active {
-            self.conf.keys.insert(key.into());
-        } else {
-            self.conf.keys.remove(key);
+impl Textures {
+    pub fn new() -> Self {
+        Self {
+            walls: HashMap::new(),
+            sprites: Vec::new(),
         }
-        match key {
-            "s" => self.conf.left = active,
-            "f" => self.conf.right = active,
-            "e" => self.conf.up = active,
-            "d" => self.conf.down = active,
-            _ => {}
+    }
+}
+
+impl Graphics for State {
+    fn input(&mut self, action: Action, value: f32) {
+        match action {
+            Action::Look => self.conf.look += value,
+            Action::Pitch => self.conf.pitch += value,
+            action => {
+                self.conf.actions.insert(action, value.clamp(0.0, 1.0));
+            }
         }
     }
     fn update(&mut self, time: f32, delta: f32, fps: f32) {
@@ -235,4 +635,7 @@ impl Graphics for State {
     fn render(&mut self, buffer: &mut [u8], width: u32, height: u32) {
         render_graphics(self, buffer, width, height);
     }
+    fn message(&mut self, text: &str) {
+        self.show(text);
+    }
 }
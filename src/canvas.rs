@@ -1,5 +1,9 @@
 pub mod background;
+pub mod circle;
 pub mod graphics;
 pub mod line;
 pub mod point;
+pub mod polygon;
 pub mod rectangle;
+pub mod text;
+pub mod texture;
@@ -1,15 +1,24 @@
-use crate::graphics::color::Color;
+use crate::graphics::color::{Blend, Color};
 
-pub fn render(buffer: &mut [u8], color: &Color) {
+use super::point;
+
+pub fn render(buffer: &mut [u8], color: &Color, blend: Blend) {
     let length = buffer.len();
 
+    // Opaque fills skip reading the destination
+    let opaque = blend == Blend::Replace || (blend == Blend::Normal && color.alpha == 255);
+
     let mut point = 0;
 
     loop {
-        buffer[point + 0] = color.red;
-        buffer[point + 1] = color.green;
-        buffer[point + 2] = color.blue;
-        buffer[point + 3] = color.alpha;
+        if opaque {
+            buffer[point + 0] = color.red;
+            buffer[point + 1] = color.green;
+            buffer[point + 2] = color.blue;
+            buffer[point + 3] = color.alpha;
+        } else {
+            point::composite(buffer, point, color, blend);
+        }
 
         point += 4;
 
@@ -0,0 +1,156 @@
+use crate::graphics::circle::{Arc, Circle, Ellipse};
+use crate::graphics::color::{Blend, Color};
+
+use super::line;
+use super::point;
+
+pub fn render(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    circle: &Circle,
+    color: &Color,
+    blend: Blend,
+) {
+    render_ellipse(
+        buffer,
+        width,
+        height,
+        &Ellipse::from_circle(circle),
+        color,
+        blend,
+    );
+}
+
+pub fn render_outline(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    circle: &Circle,
+    color: &Color,
+    blend: Blend,
+) {
+    let ellipse = Ellipse::from_circle(circle);
+    render_ellipse_outline(buffer, width, height, &ellipse, color, blend);
+}
+
+// Fill the pixels with centers inside the ellipse, one span per row
+pub fn render_ellipse(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    ellipse: &Ellipse,
+    color: &Color,
+    blend: Blend,
+) {
+    let Ellipse {
+        radius_x, radius_y, ..
+    } = *ellipse;
+
+    for y in rows(ellipse, radius_y, height) {
+        if let Some((start, end)) = columns(ellipse, radius_x, radius_y, y) {
+            line::span(buffer, width, height, y, start, end, color, blend);
+        }
+    }
+}
+
+// Fill the ring of pixels within half a pixel of the ellipse edge, blending each pixel once
+pub fn render_ellipse_outline(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    ellipse: &Ellipse,
+    color: &Color,
+    blend: Blend,
+) {
+    let Ellipse {
+        radius_x, radius_y, ..
+    } = *ellipse;
+
+    let (outer_x, outer_y) = (radius_x + 0.5, radius_y + 0.5);
+    let (inner_x, inner_y) = (radius_x - 0.5, radius_y - 0.5);
+
+    for y in rows(ellipse, outer_y, height) {
+        let Some((start, end)) = columns(ellipse, outer_x, outer_y, y) else {
+            continue;
+        };
+
+        match columns(ellipse, inner_x, inner_y, y) {
+            Some((inner_start, inner_end)) => {
+                line::span(
+                    buffer,
+                    width,
+                    height,
+                    y,
+                    start,
+                    inner_start - 1,
+                    color,
+                    blend,
+                );
+                line::span(buffer, width, height, y, inner_end + 1, end, color, blend);
+            }
+            None => line::span(buffer, width, height, y, start, end, color, blend),
+        }
+    }
+}
+
+// Render the ring of pixels along the arc within its sweep
+pub fn render_arc(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    arc: &Arc,
+    color: &Color,
+    blend: Blend,
+) {
+    let outer = arc.radius + 0.5;
+    let inner = (arc.radius - 0.5).max(0.0);
+
+    let left = ((arc.x - outer).floor() as i32).max(0);
+    let right = ((arc.x + outer).ceil() as i32).min(width as i32 - 1);
+    let top = ((arc.y - outer).floor() as i32).max(0);
+    let bottom = ((arc.y + outer).ceil() as i32).min(height as i32 - 1);
+
+    for y in top..=bottom {
+        for x in left..=right {
+            let dx = x as f32 + 0.5 - arc.x;
+            let dy = y as f32 + 0.5 - arc.y;
+            let distance = dx.hypot(dy);
+
+            if distance < inner || distance > outer || !arc.covers(dy.atan2(dx)) {
+                continue;
+            }
+
+            let index = (x as usize + y as usize * width as usize) * 4;
+            point::composite(buffer, index, color, blend);
+        }
+    }
+}
+
+// Buffer rows the ellipse may cover
+fn rows(ellipse: &Ellipse, radius_y: f32, height: u32) -> std::ops::Range<i32> {
+    let top = ((ellipse.y - radius_y).floor() as i32).max(0);
+    let bottom = ((ellipse.y + radius_y).ceil() as i32).min(height as i32);
+
+    top..bottom
+}
+
+// First and last column with pixel centers inside the ellipse on a row
+fn columns(ellipse: &Ellipse, radius_x: f32, radius_y: f32, y: i32) -> Option<(i32, i32)> {
+    if radius_x <= 0.0 || radius_y <= 0.0 {
+        return None;
+    }
+
+    let dy = (y as f32 + 0.5 - ellipse.y) / radius_y;
+
+    if dy.abs() > 1.0 {
+        return None;
+    }
+
+    let half = radius_x * (1.0 - dy * dy).sqrt();
+
+    let start = (ellipse.x - half - 0.5).ceil() as i32;
+    let end = (ellipse.x + half - 0.5).floor() as i32;
+
+    (start <= end).then_some((start, end))
+}
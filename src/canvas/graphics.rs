@@ -1,29 +1,89 @@
 use crate::graphics::graphic::Graphic;
+use crate::graphics::line::Stroke;
 
 use super::background;
+use super::circle;
 use super::line;
 use super::point;
+use super::polygon;
 use super::rectangle;
+use super::text;
 
 pub fn render(buffer: &mut [u8], width: u32, height: u32, graphics: &Vec<&Graphic>) {
     for graphic in graphics.iter() {
         match graphic {
-            Graphic::Background { color } => {
-                background::render(buffer, color);
+            Graphic::Background { color, blend } => {
+                background::render(buffer, color, *blend);
             }
-            Graphic::Point { point, color } => {
-                point::render(buffer, width, height, point, color);
+            Graphic::Point {
+                point,
+                color,
+                blend,
+            } => {
+                point::render(buffer, width, height, point, color, *blend);
             }
-            Graphic::Line { line, color } => {
-                line::render(buffer, width, height, line, color);
+            Graphic::Line {
+                line,
+                color,
+                blend,
+                stroke,
+            } => {
+                if *stroke == Stroke::Smooth {
+                    line::render_smooth(buffer, width, height, line, color, *blend);
+                } else {
+                    line::render(buffer, width, height, line, color, *blend);
+                }
             }
-            Graphic::Rectangle { rectangle, color } => {
+            Graphic::Rectangle {
+                rectangle,
+                color,
+                blend,
+            } => {
                 if rectangle.solid {
-                    rectangle::render(buffer, width, height, rectangle, color);
+                    rectangle::render(buffer, width, height, rectangle, color, *blend);
                 } else {
-                    rectangle::render_outline(buffer, width, height, rectangle, color);
+                    rectangle::render_outline(buffer, width, height, rectangle, color, *blend);
                 }
             }
+            Graphic::Circle {
+                circle,
+                color,
+                blend,
+            } => {
+                if circle.solid {
+                    circle::render(buffer, width, height, circle, color, *blend);
+                } else {
+                    circle::render_outline(buffer, width, height, circle, color, *blend);
+                }
+            }
+            Graphic::Ellipse {
+                ellipse,
+                color,
+                blend,
+            } => {
+                if ellipse.solid {
+                    circle::render_ellipse(buffer, width, height, ellipse, color, *blend);
+                } else {
+                    circle::render_ellipse_outline(buffer, width, height, ellipse, color, *blend);
+                }
+            }
+            Graphic::Arc { arc, color, blend } => {
+                circle::render_arc(buffer, width, height, arc, color, *blend);
+            }
+            Graphic::Polygon {
+                polygon,
+                color,
+                blend,
+            } => {
+                if polygon.solid {
+                    polygon::render(buffer, width, height, polygon, color, *blend);
+                } else {
+                    polygon::render_outline(buffer, width, height, polygon, color, *blend);
+                }
+            }
+            Graphic::Text { text, color, blend } => {
+                text::render(buffer, width, height, text, color, *blend);
+            }
             _ => {}
         }
     }
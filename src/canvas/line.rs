@@ -1,9 +1,23 @@
-use crate::graphics::color::Color;
+use std::mem;
+
+use crate::graphics::color::{Blend, Color};
 use crate::graphics::line::Line;
 
 use super::point;
 
-pub fn render(buffer: &mut [u8], width: u32, height: u32, line: &Line, color: &Color) {
+pub fn render(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    line: &Line,
+    color: &Color,
+    blend: Blend,
+) {
+    // Clip rather than clamp the endpoints, so lines leaving the buffer keep their slope
+    let Some(line) = line.clip(0.0, 0.0, width as f32, height as f32) else {
+        return;
+    };
+
     let width = width as usize;
     let height = height as usize;
 
@@ -13,32 +27,116 @@ pub fn render(buffer: &mut [u8], width: u32, height: u32, line: &Line, color: &C
     let x1 = line.x1.round() as i32;
     let y1 = line.y1.round() as i32;
 
-    if x0 == x1 && (x0 < 0 || x0 >= width as i32) {
-        return;
-    }
-
-    if y0 == y1 && (y0 < 0 || y0 >= height as i32) {
-        return;
-    }
-
-    let x0 = x0.clamp(0, width as i32);
-    let y0 = y0.clamp(0, height as i32);
-
-    let x1 = x1.clamp(0, width as i32);
-    let y1 = y1.clamp(0, height as i32);
-
     if (x1 - x0).abs() > (y1 - y0).abs() {
         if x0 < x1 {
-            low(buffer, width, height, color, x0, y0, x1, y1);
+            low(buffer, width, height, color, blend, x0, y0, x1, y1);
         } else {
-            low(buffer, width, height, color, x1, y1, x0, y0);
+            low(buffer, width, height, color, blend, x1, y1, x0, y0);
         }
     } else {
         if y0 < y1 {
-            high(buffer, width, height, color, x0, y0, x1, y1);
+            high(buffer, width, height, color, blend, x0, y0, x1, y1);
         } else {
-            high(buffer, width, height, color, x1, y1, x0, y0);
+            high(buffer, width, height, color, blend, x1, y1, x0, y0);
+        }
+    }
+}
+
+// Render line anti-aliased with Xiaolin Wu's algorithm, scaling the color alpha by pixel coverage
+pub fn render_smooth(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    line: &Line,
+    color: &Color,
+    blend: Blend,
+) {
+    // Pixels along the border are partially covered by lines just outside
+    let Some(line) = line.clip(-1.0, -1.0, width as f32, height as f32) else {
+        return;
+    };
+
+    let Line {
+        mut x0,
+        mut y0,
+        mut x1,
+        mut y1,
+    } = line;
+
+    // Step along the major axis
+    let steep = (y1 - y0).abs() > (x1 - x0).abs();
+
+    if steep {
+        mem::swap(&mut x0, &mut y0);
+        mem::swap(&mut x1, &mut y1);
+    }
+
+    if x0 > x1 {
+        mem::swap(&mut x0, &mut x1);
+        mem::swap(&mut y0, &mut y1);
+    }
+
+    let dx = x1 - x0;
+    let dy = y1 - y0;
+    let gradient = if dx == 0.0 { 1.0 } else { dy / dx };
+
+    let fraction = |value: f32| value - value.floor();
+
+    let mut plot = |x: i32, y: i32, coverage: f32| {
+        let (x, y) = if steep { (y, x) } else { (x, y) };
+
+        if coverage <= 0.0 || x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+            return;
         }
+
+        let alpha = (color.alpha as f32 * coverage.min(1.0)).round() as u8;
+        let color = Color::new(color.red, color.green, color.blue, alpha);
+
+        let index = (x as usize + y as usize * width as usize) * 4;
+        point::composite(buffer, index, &color, blend);
+    };
+
+    // Endpoints are covered by the part of their pixel the line spans
+    let start = x0.round();
+    let start_y = y0 + gradient * (start - x0);
+    let start_gap = 1.0 - fraction(x0 + 0.5);
+
+    plot(
+        start as i32,
+        start_y.floor() as i32,
+        (1.0 - fraction(start_y)) * start_gap,
+    );
+    plot(
+        start as i32,
+        start_y.floor() as i32 + 1,
+        fraction(start_y) * start_gap,
+    );
+
+    let end = x1.round();
+    let end_y = y1 + gradient * (end - x1);
+    let end_gap = fraction(x1 + 0.5);
+
+    if end > start {
+        plot(
+            end as i32,
+            end_y.floor() as i32,
+            (1.0 - fraction(end_y)) * end_gap,
+        );
+        plot(
+            end as i32,
+            end_y.floor() as i32 + 1,
+            fraction(end_y) * end_gap,
+        );
+    }
+
+    // Split each column between the two pixels the line passes through
+    let mut y = start_y + gradient;
+
+    for x in (start as i32 + 1)..(end as i32) {
+        plot(x, y.floor() as i32, 1.0 - fraction(y));
+        plot(x, y.floor() as i32 + 1, fraction(y));
+
+        y += gradient;
     }
 }
 
@@ -47,6 +145,7 @@ fn low(
     width: usize,
     height: usize,
     color: &Color,
+    blend: Blend,
     x0: i32,
     y0: i32,
     x1: i32,
@@ -62,7 +161,7 @@ fn low(
     let mut d = (dy * 2) - dx;
     let mut y = y0;
     for x in x0..x1 {
-        point::plot(buffer, width, height, color, x as usize, y as usize);
+        point::plot(buffer, width, height, color, x as usize, y as usize, blend);
         if d > 0 {
             y = y + yi;
             d = d + ((dy - dx) * 2)
@@ -77,6 +176,7 @@ fn high(
     width: usize,
     height: usize,
     color: &Color,
+    blend: Blend,
     x0: i32,
     y0: i32,
     x1: i32,
@@ -92,7 +192,7 @@ fn high(
     let mut d = (dx * 2) - dy;
     let mut x = x0;
     for y in y0..y1 {
-        point::plot(buffer, width, height, color, x as usize, y as usize);
+        point::plot(buffer, width, height, color, x as usize, y as usize, blend);
         if d > 0 {
             x = x + xi;
             d = d + ((dx - dy) * 2)
@@ -102,6 +202,29 @@ fn high(
     }
 }
 
+// Fill columns start..=end of a row, clipped to the buffer
+pub fn span(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    y: i32,
+    start: i32,
+    end: i32,
+    color: &Color,
+    blend: Blend,
+) {
+    let start = start.max(0);
+    let end = end.min(width as i32 - 1);
+
+    if start > end || y < 0 || y >= height as i32 {
+        return;
+    }
+
+    let length = (end - start + 1) as u32;
+
+    horizontal(buffer, width, height, start, y, length, color, blend);
+}
+
 pub fn horizontal(
     buffer: &mut [u8],
     width: u32,
@@ -110,6 +233,7 @@ pub fn horizontal(
     y: i32,
     length: u32,
     color: &Color,
+    blend: Blend,
 ) {
     let width = width as i32;
     let height = height as i32;
@@ -139,10 +263,7 @@ pub fn horizontal(
 
         let index = (x + y * width) * 4;
 
-        buffer[index as usize + 0] = color.red;
-        buffer[index as usize + 1] = color.green;
-        buffer[index as usize + 2] = color.blue;
-        buffer[index as usize + 3] = color.alpha;
+        point::composite(buffer, index as usize, color, blend);
 
         i += 4;
 
@@ -160,6 +281,7 @@ pub fn vertical(
     y: i32,
     length: u32,
     color: &Color,
+    blend: Blend,
 ) {
     let width = width as i32;
     let height = height as i32;
@@ -173,6 +295,11 @@ pub fn vertical(
     let mut y = y;
 
     if y < 0 {
+        // Nothing left once clipped away entirely above the buffer
+        if y + length <= 0 {
+            return;
+        }
+
         length = length + y;
         y = 0;
     }
@@ -189,10 +316,7 @@ pub fn vertical(
 
         let index = (x + y * width) * 4;
 
-        buffer[index as usize + 0] = color.red;
-        buffer[index as usize + 1] = color.green;
-        buffer[index as usize + 2] = color.blue;
-        buffer[index as usize + 3] = color.alpha;
+        point::composite(buffer, index as usize, color, blend);
 
         i += 4;
 
@@ -1,7 +1,14 @@
-use crate::graphics::color::Color;
+use crate::graphics::color::{Blend, Color};
 use crate::graphics::point::Point;
 
-pub fn render(buffer: &mut [u8], width: u32, height: u32, point: &Point, color: &Color) {
+pub fn render(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    point: &Point,
+    color: &Color,
+    blend: Blend,
+) {
     let x = point.x.round();
     let y = point.y.round();
 
@@ -11,21 +18,36 @@ pub fn render(buffer: &mut [u8], width: u32, height: u32, point: &Point, color:
 
     let index = (x as usize + y as usize * width as usize) * 4;
 
-    buffer[index + 0] = color.red;
-    buffer[index + 1] = color.green;
-    buffer[index + 2] = color.blue;
-    buffer[index + 3] = color.alpha;
+    composite(buffer, index, color, blend);
 }
 
-pub fn plot(buffer: &mut [u8], width: usize, _height: usize, color: &Color, x: usize, y: usize) {
+pub fn plot(
+    buffer: &mut [u8],
+    width: usize,
+    height: usize,
+    color: &Color,
+    x: usize,
+    y: usize,
+    blend: Blend,
+) {
     let index = (x + y * width) * 4;
 
-    if index + 3 > buffer.len() {
+    if x >= width || y >= height || index + 3 > buffer.len() {
         return;
     }
 
-    buffer[index + 0] = color.red;
-    buffer[index + 1] = color.green;
-    buffer[index + 2] = color.blue;
-    buffer[index + 3] = color.alpha;
+    composite(buffer, index, color, blend);
+}
+
+// Blend color into the pixel starting at byte index
+pub fn composite(buffer: &mut [u8], index: usize, color: &Color, blend: Blend) {
+    let pixel = &mut buffer[index..index + 4];
+
+    let destination = Color::new(pixel[0], pixel[1], pixel[2], pixel[3]);
+    let color = color.blend(destination, blend);
+
+    pixel[0] = color.red;
+    pixel[1] = color.green;
+    pixel[2] = color.blue;
+    pixel[3] = color.alpha;
 }
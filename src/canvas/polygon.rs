@@ -0,0 +1,80 @@
+use crate::graphics::color::{Blend, Color};
+use crate::graphics::polygon::{crossing, Fill, Polygon};
+
+use super::line;
+
+// Fill the pixels with centers inside the polygon, row by row
+pub fn render(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    polygon: &Polygon,
+    color: &Color,
+    blend: Blend,
+) {
+    if polygon.points.len() < 3 {
+        return;
+    }
+
+    let top = polygon
+        .points
+        .iter()
+        .map(|point| point.y)
+        .fold(f32::MAX, f32::min);
+    let bottom = polygon
+        .points
+        .iter()
+        .map(|point| point.y)
+        .fold(f32::MIN, f32::max);
+
+    let first = (top.floor() as i32).max(0);
+    let last = (bottom.ceil() as i32).min(height as i32);
+
+    let edges: Vec<_> = polygon.edges().collect();
+    let mut crossings = Vec::with_capacity(edges.len());
+
+    for y in first..last {
+        let center = y as f32 + 0.5;
+
+        crossings.clear();
+        crossings.extend(edges.iter().filter_map(|edge| crossing(edge, center)));
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut winding = 0;
+
+        for pair in crossings.windows(2) {
+            let (left, direction) = pair[0];
+            let (right, _) = pair[1];
+
+            winding += direction;
+
+            let inside = match polygon.fill {
+                Fill::EvenOdd => winding % 2 != 0,
+                Fill::NonZero => winding != 0,
+            };
+
+            if !inside {
+                continue;
+            }
+
+            // Pixels with centers between the crossings
+            let start = (left - 0.5).ceil() as i32;
+            let end = (right - 0.5).ceil() as i32 - 1;
+
+            line::span(buffer, width, height, y, start, end, color, blend);
+        }
+    }
+}
+
+pub fn render_outline(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    polygon: &Polygon,
+    color: &Color,
+    blend: Blend,
+) {
+    for edge in polygon.edges() {
+        line::render(buffer, width, height, &edge, color, blend);
+    }
+}
@@ -1,10 +1,18 @@
-use crate::graphics::color::Color;
+use crate::graphics::color::{Blend, Color};
 use crate::graphics::line::Line;
 use crate::graphics::rectangle::Rectangle;
 
 use super::line;
+use super::point;
 
-pub fn render(buffer: &mut [u8], width: u32, height: u32, rectangle: &Rectangle, color: &Color) {
+pub fn render(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    rectangle: &Rectangle,
+    color: &Color,
+    blend: Blend,
+) {
     let width = width as i32;
     let height = height as i32;
 
@@ -51,10 +59,7 @@ pub fn render(buffer: &mut [u8], width: u32, height: u32, rectangle: &Rectangle,
 
         let index = (x + y * width) * 4;
 
-        buffer[index as usize + 0] = color.red;
-        buffer[index as usize + 1] = color.green;
-        buffer[index as usize + 2] = color.blue;
-        buffer[index as usize + 3] = color.alpha;
+        point::composite(buffer, index as usize, color, blend);
 
         point += 4;
 
@@ -70,6 +75,7 @@ pub fn render_outline(
     height: u32,
     rectangle: &Rectangle,
     color: &Color,
+    blend: Blend,
 ) {
     let x = rectangle.x;
     let y = rectangle.y;
@@ -82,11 +88,12 @@ pub fn render_outline(
 
     let left = Line::new(x, y, x, y + h);
     let right = Line::new(x + w, y, x + w, y + h);
-    let top = Line::new(x, y, x + w, y);
+    // Start past the corner the left edge already covers, blending each pixel once
+    let top = Line::new(x + 1.0, y, x + w, y);
     let bottom = Line::new(x, y + h, x + w, y + h);
 
-    line::render(buffer, width, height, &left, color);
-    line::render(buffer, width, height, &right, color);
-    line::render(buffer, width, height, &top, color);
-    line::render(buffer, width, height, &bottom, color);
+    line::render(buffer, width, height, &left, color, blend);
+    line::render(buffer, width, height, &right, color, blend);
+    line::render(buffer, width, height, &top, color, blend);
+    line::render(buffer, width, height, &bottom, color, blend);
 }
@@ -0,0 +1,51 @@
+use crate::graphics::color::{Blend, Color};
+use crate::graphics::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use crate::graphics::text::Text;
+
+use super::line;
+
+// Render each line of the text, drawing runs of set glyph pixels as scaled spans
+pub fn render(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    text: &Text,
+    color: &Color,
+    blend: Blend,
+) {
+    let scale = text.scale.max(1) as i32;
+
+    for (row, content) in text.lines().enumerate() {
+        let top = (text.y + row as f32 * text.line_height()).round() as i32;
+        let left = text.left(content).round() as i32;
+
+        for (column, character) in content.chars().enumerate() {
+            let x = left + (column as f32 * text.advance()) as i32;
+
+            for glyph_y in 0..GLYPH_HEIGHT {
+                let mut glyph_x = 0;
+
+                while glyph_x < GLYPH_WIDTH {
+                    if !font::pixel(character, glyph_x, glyph_y) {
+                        glyph_x += 1;
+                        continue;
+                    }
+
+                    let start = glyph_x;
+
+                    while glyph_x < GLYPH_WIDTH && font::pixel(character, glyph_x, glyph_y) {
+                        glyph_x += 1;
+                    }
+
+                    let first = x + start as i32 * scale;
+                    let last = x + glyph_x as i32 * scale - 1;
+
+                    for offset in 0..scale {
+                        let y = top + glyph_y as i32 * scale + offset;
+                        line::span(buffer, width, height, y, first, last, color, blend);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::graphics::color::{Blend, Color};
+use crate::graphics::texture::Texture;
+
+use super::point;
+
+// Render vertical texture strip, repeating texture column `u` `span` times over the strip length
+// aligned to its bottom edge, and passing each opaque texel through `filter`
+pub fn column(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    x: i32,
+    y: f32,
+    length: f32,
+    texture: &Texture,
+    u: f32,
+    span: f32,
+    first: u32,
+    filter: &dyn Fn(Color) -> Color,
+) {
+    if x < 0 || x >= width as i32 || length <= 0.0 || texture.width == 0 || texture.height == 0 {
+        return;
+    }
+
+    // Rows are absolute, the buffer holding `height` rows from row `first`
+    let start = y.max(first as f32) as i32;
+    let end = (y + length).min((first + height) as f32) as i32;
+
+    let texture_x = ((u.rem_euclid(1.0) * texture.width as f32) as u32).min(texture.width - 1);
+    // Texel rows over the whole strip, shifted so that a partial repeat is cut off at the top
+    let repeats = span.ceil().max(1.0);
+    let step = texture.height as f32 * span / length;
+    let shift = texture.height as f32 * (repeats - span);
+    let last = repeats as u32 * texture.height - 1;
+
+    for row in start..end {
+        let texel = ((row as f32 + 0.5 - y) * step + shift) as u32;
+        let texture_y = texel.min(last) % texture.height;
+        let color = texture.pixel(texture_x, texture_y);
+
+        if color.alpha == 0 {
+            continue;
+        }
+
+        let color = filter(color);
+        let index = ((x + (row - first as i32) * width as i32) * 4) as usize;
+
+        point::composite(buffer, index, &color, Blend::Normal);
+    }
+}
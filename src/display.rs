@@ -1,3 +1,4 @@
+pub mod headless;
 pub mod surface;
 pub mod window;
 
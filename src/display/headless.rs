@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use image::{ImageResult, RgbaImage};
+
+use crate::input::backend::{Backend, Input};
+
+use super::window::Graphics;
+
+pub struct HeadlessState {
+    buffer: Vec<u8>,
+    width: u32,
+    height: u32,
+    time: f32,
+    input: Input,
+}
+
+impl HeadlessState {
+    pub fn new(width: u32, height: u32) -> Self {
+        let length = (width * height * 4) as usize;
+
+        Self {
+            buffer: vec![0u8; length],
+            width,
+            height,
+            time: 0.0,
+            input: Input::default(),
+        }
+    }
+
+    // Attach input backend polled before each frame update
+    pub fn attach(&mut self, backend: impl Backend + 'static) {
+        self.input.attach(backend);
+    }
+
+    pub fn buffer(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // Update and render a single frame with a fixed delta time
+    pub fn frame(&mut self, graphics: &mut impl Graphics, delta: f32) -> &[u8] {
+        self.time += delta;
+
+        for (action, value) in self.input.poll(self.time) {
+            graphics.input(action, value);
+        }
+
+        let fps = if delta > 0.0 { 1.0 / delta } else { 0.0 };
+
+        graphics.update(self.time, delta, fps);
+        graphics.render(&mut self.buffer, self.width, self.height);
+
+        &self.buffer
+    }
+
+    // Copy frame buffer into an opaque image, as presented by the window
+    pub fn image(&self) -> RgbaImage {
+        let mut image = RgbaImage::from_raw(self.width, self.height, self.buffer.clone())
+            .expect("Headless buffer should match image dimensions");
+
+        for pixel in image.pixels_mut() {
+            pixel[3] = 255;
+        }
+
+        image
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        self.image().save(path)
+    }
+}
+
+// Render a number of frames without a window
+fn render(
+    width: u32,
+    height: u32,
+    frames: u32,
+    delta: f32,
+    state: &mut impl Graphics,
+) -> HeadlessState {
+    let mut headless = HeadlessState::new(width, height);
+
+    for _ in 0..frames.max(1) {
+        headless.frame(state, delta);
+    }
+
+    headless
+}
+
+// Render a number of frames without a window and return the final frame buffer
+pub fn capture(
+    width: u32,
+    height: u32,
+    frames: u32,
+    delta: f32,
+    state: &mut impl Graphics,
+) -> Vec<u8> {
+    render(width, height, frames, delta, state).buffer
+}
+
+// Render a number of frames without a window and save the final frame as image
+pub fn run(
+    width: u32,
+    height: u32,
+    frames: u32,
+    delta: f32,
+    path: impl AsRef<Path>,
+    state: &mut impl Graphics,
+) -> ImageResult<()> {
+    render(width, height, frames, delta, state).save(path)
+}
@@ -62,6 +62,7 @@ pub struct SurfaceState<'a> {
     index_number: u32,
     bind_group: wgpu::BindGroup,
     texture: wgpu::Texture,
+    adapter: String,
 }
 
 impl<'a> SurfaceState<'a> {
@@ -299,7 +300,7 @@ impl<'a> SurfaceState<'a> {
 
         surface.configure(&device, &surface_configuration);
 
-        println!("{}", adapter.get_info().name);
+        let adapter = adapter.get_info().name;
 
         Self {
             buffer,
@@ -316,6 +317,7 @@ impl<'a> SurfaceState<'a> {
             index_number,
             bind_group,
             texture,
+            adapter,
         }
     }
 
@@ -353,6 +355,11 @@ impl<'a> SurfaceState<'a> {
         &self.window
     }
 
+    // Name of the graphics adapter the surface renders with
+    pub fn adapter(&self) -> &str {
+        &self.adapter
+    }
+
     pub fn resize(&mut self, size: winit::dpi::PhysicalSize<u32>) {
         if size.width > 0 && size.height > 0 {
             self.surface_configuration.width = size.width;
@@ -3,17 +3,22 @@ use std::time::Instant;
 
 use winit::application::ApplicationHandler;
 use winit::dpi::{LogicalSize, Size};
-use winit::event::{ElementState, KeyEvent, WindowEvent};
+use winit::event::{DeviceEvent, DeviceId, ElementState, KeyEvent, MouseButton, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
-use winit::keyboard::{Key, KeyCode, PhysicalKey};
-use winit::window::{Window, WindowId};
+use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::window::{CursorGrabMode, Window, WindowId};
+
+use crate::input::backend::Input;
+use crate::input::controls::Action;
 
 use super::surface::SurfaceState;
 
 pub trait Graphics {
-    fn input(&mut self, active: bool, key: &str);
+    fn input(&mut self, action: Action, value: f32);
     fn update(&mut self, time: f32, delta: f32, fps: f32);
     fn render(&mut self, buffer: &mut [u8], width: u32, height: u32);
+    // Show a message to the player, ignored unless implemented
+    fn message(&mut self, _text: &str) {}
 }
 
 #[derive(Debug, Clone)]
@@ -46,12 +51,30 @@ impl WindowConfiguration {
     }
 }
 
+// Input and update options of a window run
+pub struct WindowOptions {
+    pub timestep: Option<f32>, // Fixed update step in seconds, or one update per frame
+    pub input: Input,          // Keybindings and attached input backends
+}
+
+impl WindowOptions {
+    pub fn new(timestep: Option<f32>, input: Input) -> Self {
+        Self { timestep, input }
+    }
+}
+
+// Maximum fixed updates per frame, dropping time beyond it to avoid spiraling
+const MAX_STEPS: u32 = 8;
+
 struct WindowTime {
     pub elapsed: f32,
     pub delta: f32,
     pub fps: f32,
+    pub average: f32,
+    pub simulated: f32,
     instant: Instant,
     previous: f32,
+    accumulator: f32,
     total: f32,
     number: u32,
     samples: u32,
@@ -63,8 +86,11 @@ impl WindowTime {
             elapsed: 0.0,
             delta: 0.0,
             fps: 0.0,
+            average: 0.0,
+            simulated: 0.0,
             instant: Instant::now(),
             previous: 0.0,
+            accumulator: 0.0,
             total: 0.0,
             number: 0,
             samples: 60,
@@ -78,9 +104,19 @@ impl WindowTime {
         self.total += self.fps;
         self.number += 1;
     }
-    fn print(&mut self) {
-        if self.number == self.samples {
-            println!("fps: {:.1}", self.total / self.number as f32);
+    // Accumulate frame time and return the number of fixed steps to simulate
+    fn steps(&mut self, step: f32) -> u32 {
+        self.accumulator = (self.accumulator + self.delta).min(step * MAX_STEPS as f32);
+
+        let steps = (self.accumulator / step).floor() as u32;
+        self.accumulator -= steps as f32 * step;
+
+        steps
+    }
+    // Average frame rate over the last samples, starting from the first frame
+    fn sample(&mut self) {
+        if self.number == self.samples || self.average == 0.0 {
+            self.average = self.total / self.number as f32;
             self.total = 0.0;
             self.number = 0;
         }
@@ -92,35 +128,71 @@ struct WindowState<'a> {
     window: Option<Arc<Window>>,
     surface: Option<SurfaceState<'a>>,
     graphics: Option<Box<dyn Graphics>>,
+    input: Input,
+    timestep: Option<f32>,
     time: WindowTime,
+    captured: bool,
 }
 
 impl<'a> WindowState<'a> {
-    fn new(config: WindowConfiguration) -> Self {
+    fn new(config: WindowConfiguration, options: WindowOptions) -> Self {
         Self {
             config,
             window: None,
             surface: None,
             graphics: None,
+            input: options.input,
+            timestep: options.timestep,
             time: WindowTime::new(),
+            captured: false,
+        }
+    }
+    // Lock and hide the cursor for mouse look, or release it back to the user
+    fn capture(&mut self, captured: bool) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+
+        if captured {
+            // Fall back to confining the cursor to the window where locking is unsupported
+            let _ = window
+                .set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined));
+        } else {
+            let _ = window.set_cursor_grab(CursorGrabMode::None);
+        }
+
+        window.set_cursor_visible(!captured);
+
+        self.captured = captured;
+    }
+    // Forward button event through keybindings to graphics
+    fn button(&mut self, name: &str, active: bool) {
+        if let Some((action, value)) = self.input.keyboard.button(name, active) {
+            if let Some(graphics) = self.graphics.as_mut() {
+                graphics.input(action, value);
+            }
         }
     }
 }
 
+// Get button name for mouse button, matching key code names
+fn mouse_button(button: MouseButton) -> String {
+    match button {
+        MouseButton::Other(number) => format!("Mouse{number}"),
+        button => format!("Mouse{button:?}"),
+    }
+}
+
 pub fn run(
-    width: u32,
-    height: u32,
-    scale: f32,
-    resizable: bool,
-    filter: bool,
-    title: String,
+    configuration: WindowConfiguration,
+    options: WindowOptions,
     state: impl Graphics + 'static,
 ) {
     let event_loop = EventLoop::new().expect("Window event loop should instantiate");
     event_loop.set_control_flow(ControlFlow::Poll);
     // event_loop.set_control_flow(ControlFlow::Wait);
-    let configuration = WindowConfiguration::new(width, height, scale, resizable, filter, title);
-    let mut window_state = WindowState::new(configuration);
+    let mut window_state = WindowState::new(configuration, options);
     window_state.graphics = Some(Box::new(state));
     event_loop
         .run_app(&mut window_state)
@@ -149,11 +221,32 @@ impl<'a> ApplicationHandler for WindowState<'a> {
             let surface =
                 pollster::block_on(SurfaceState::new(self.config.clone(), window.clone()));
 
+            if let Some(graphics) = self.graphics.as_mut() {
+                graphics.message(surface.adapter());
+            }
+
             self.surface = Some(surface);
 
+            self.capture(true);
+
             window.request_redraw();
         }
     }
+    fn device_event(&mut self, _event_loop: &ActiveEventLoop, _id: DeviceId, event: DeviceEvent) {
+        if !self.captured {
+            return;
+        }
+
+        if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
+            let actions = self.input.keyboard.motion(x as f32, y as f32);
+
+            if let Some(graphics) = self.graphics.as_mut() {
+                for (action, value) in actions {
+                    graphics.input(action, value);
+                }
+            }
+        }
+    }
     fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
         if self.window.is_some() && self.surface.is_some() {
             if id != self.window.as_ref().expect("Window should exist").id() {
@@ -180,29 +273,36 @@ impl<'a> ApplicationHandler for WindowState<'a> {
                         },
                     ..
                 } => {
-                    let _ = self.window.take();
-                    let _ = self.surface.take();
-                    event_loop.exit();
+                    // Release the cursor first, exit once it is released
+                    if self.captured {
+                        self.capture(false);
+                    } else {
+                        let _ = self.window.take();
+                        let _ = self.surface.take();
+                        event_loop.exit();
+                    }
+                }
+                WindowEvent::Focused(false) => {
+                    self.capture(false);
                 }
                 WindowEvent::KeyboardInput {
-                    event,
+                    event:
+                        KeyEvent {
+                            physical_key: PhysicalKey::Code(code),
+                            state,
+                            ..
+                        },
                     is_synthetic: false,
                     ..
                 } => {
-                    if event.state.is_pressed() {
-                        if let Key::Character(ch) = event.logical_key.as_ref() {
-                            self.graphics
-                                .as_mut()
-                                .expect("Window graphics should exist")
-                                .input(true, &ch);
-                        }
+                    self.button(&format!("{code:?}"), state.is_pressed());
+                }
+                WindowEvent::MouseInput { state, button, .. } => {
+                    // Clicking a window without the cursor captures it again
+                    if !self.captured && state.is_pressed() {
+                        self.capture(true);
                     } else {
-                        if let Key::Character(ch) = event.logical_key.as_ref() {
-                            self.graphics
-                                .as_mut()
-                                .expect("Window graphics should exist")
-                                .input(false, &ch);
-                        }
+                        self.button(&mouse_button(button), state.is_pressed());
                     }
                 }
                 WindowEvent::RedrawRequested => {
@@ -218,12 +318,28 @@ impl<'a> ApplicationHandler for WindowState<'a> {
                     // };
 
                     self.time.run();
-                    self.time.print();
+                    self.time.sample();
 
-                    self.graphics
+                    let graphics = self
+                        .graphics
                         .as_mut()
-                        .expect("Window graphics should exist")
-                        .update(self.time.elapsed, self.time.delta, self.time.fps);
+                        .expect("Window graphics should exist");
+
+                    for (action, value) in self.input.poll(self.time.elapsed) {
+                        graphics.input(action, value);
+                    }
+
+                    match self.timestep {
+                        Some(step) => {
+                            for _ in 0..self.time.steps(step) {
+                                self.time.simulated += step;
+                                graphics.update(self.time.simulated, step, self.time.average);
+                            }
+                        }
+                        None => {
+                            graphics.update(self.time.elapsed, self.time.delta, self.time.average)
+                        }
+                    }
 
                     self.graphics
                         .as_mut()
@@ -1,5 +1,10 @@
+pub mod circle;
 pub mod color;
+pub mod font;
 pub mod graphic;
 pub mod line;
 pub mod point;
+pub mod polygon;
 pub mod rectangle;
+pub mod text;
+pub mod texture;
@@ -0,0 +1,113 @@
+use std::f32::consts::PI;
+
+use super::point::Point;
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Circle {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+    pub solid: bool,
+}
+
+impl Circle {
+    pub fn new(x: f32, y: f32, radius: f32, solid: bool) -> Self {
+        Self {
+            x,
+            y,
+            radius,
+            solid,
+        }
+    }
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        Point::new(self.x, self.y).distance(x, y) <= self.radius
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Ellipse {
+    pub x: f32,
+    pub y: f32,
+    pub radius_x: f32,
+    pub radius_y: f32,
+    pub solid: bool,
+}
+
+impl Ellipse {
+    pub fn new(x: f32, y: f32, radius_x: f32, radius_y: f32, solid: bool) -> Self {
+        Self {
+            x,
+            y,
+            radius_x,
+            radius_y,
+            solid,
+        }
+    }
+    pub fn from_circle(circle: &Circle) -> Self {
+        Self::new(
+            circle.x,
+            circle.y,
+            circle.radius,
+            circle.radius,
+            circle.solid,
+        )
+    }
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        if self.radius_x <= 0.0 || self.radius_y <= 0.0 {
+            return false;
+        }
+
+        let dx = (x - self.x) / self.radius_x;
+        let dy = (y - self.y) / self.radius_y;
+
+        dx * dx + dy * dy <= 1.0
+    }
+}
+
+// Circle outline between two angles, running from start in the direction of increasing angle
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Arc {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Arc {
+    pub fn new(x: f32, y: f32, radius: f32, start: f32, end: f32) -> Self {
+        Self {
+            x,
+            y,
+            radius,
+            start,
+            end,
+        }
+    }
+    // Angle swept from start to end, a full turn at most
+    pub fn sweep(&self) -> f32 {
+        (self.end - self.start).clamp(-PI * 2.0, PI * 2.0)
+    }
+    // Angle within the sweep of the arc
+    pub fn covers(&self, angle: f32) -> bool {
+        let sweep = self.sweep();
+
+        if sweep.abs() >= PI * 2.0 {
+            return true;
+        }
+
+        let (start, sweep) = if sweep < 0.0 {
+            (self.start + sweep, -sweep)
+        } else {
+            (self.start, sweep)
+        };
+
+        (angle - start).rem_euclid(PI * 2.0) <= sweep
+    }
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let margin = 0.5;
+        let distance = Point::new(self.x, self.y).distance(x, y);
+
+        (distance - self.radius).abs() < margin && self.covers((y - self.y).atan2(x - self.x))
+    }
+}
@@ -1,3 +1,13 @@
+// Compositing of a source color over the destination pixel, weighted by the source alpha
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum Blend {
+    #[default]
+    Normal, // Source over destination
+    Add,      // Add the source to the destination
+    Multiply, // Multiply the destination by the source
+    Replace,  // Overwrite the destination, alpha included
+}
+
 #[derive(Debug, Copy, Clone, Default)]
 pub struct Color {
     pub red: u8,
@@ -35,6 +45,66 @@ impl Color {
             Color::rgb(red, green, blue)
         }
     }
+    pub fn scale(self, factor: f32) -> Color {
+        let channel = |value: u8| (value as f32 * factor).clamp(0.0, 255.0) as u8;
+        Color::new(
+            channel(self.red),
+            channel(self.green),
+            channel(self.blue),
+            self.alpha,
+        )
+    }
+    pub fn mix(self, other: Color, amount: f32) -> Color {
+        let amount = amount.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amount).round() as u8;
+        Color::new(
+            channel(self.red, other.red),
+            channel(self.green, other.green),
+            channel(self.blue, other.blue),
+            self.alpha,
+        )
+    }
+    // Composite over a destination color, alpha is non-premultiplied
+    pub fn blend(self, destination: Color, blend: Blend) -> Color {
+        if blend == Blend::Replace || (self.alpha == 255 && blend == Blend::Normal) {
+            return self;
+        }
+
+        if self.alpha == 0 {
+            return destination;
+        }
+
+        let source_alpha = self.alpha as f32 / 255.0;
+        let destination_alpha = destination.alpha as f32 / 255.0;
+
+        // Destination coverage left visible through the source
+        let behind = destination_alpha * (1.0 - source_alpha);
+        let alpha = source_alpha + behind;
+
+        let channel = |source: u8, destination: u8| {
+            let source = source as f32 / 255.0;
+            let destination = destination as f32 / 255.0;
+
+            let mixed = match blend {
+                Blend::Add => source + destination,
+                Blend::Multiply => source * destination,
+                _ => source,
+            };
+
+            // Blended color where the destination is covered, plain source elsewhere
+            let source = source + (mixed - source) * destination_alpha;
+            let value = (source * source_alpha + destination * behind) / alpha;
+
+            (value * 255.0).round().clamp(0.0, 255.0) as u8
+        };
+
+        Color::new(
+            channel(self.red, destination.red),
+            channel(self.green, destination.green),
+            channel(self.blue, destination.blue),
+            (alpha * 255.0).round() as u8,
+        )
+    }
     pub fn rgb(red: u8, green: u8, blue: u8) -> u32 {
         ((red as u32) << 16) | ((green as u32) << 8) | (blue as u32)
     }
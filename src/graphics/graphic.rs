@@ -1,7 +1,10 @@
-use super::color::Color;
-use super::line::Line;
+use super::circle::{Arc, Circle, Ellipse};
+use super::color::{Blend, Color};
+use super::line::{Line, Stroke};
 use super::point::Point;
+use super::polygon::Polygon;
 use super::rectangle::Rectangle;
+use super::text::Text;
 
 #[derive(Debug, Clone, Default)]
 pub enum Graphic {
@@ -9,18 +12,48 @@ pub enum Graphic {
     None,
     Background {
         color: Color,
+        blend: Blend,
     },
     Point {
         point: Point,
         color: Color,
+        blend: Blend,
     },
     Line {
         line: Line,
         color: Color,
+        blend: Blend,
+        stroke: Stroke,
     },
     Rectangle {
         rectangle: Rectangle,
         color: Color,
+        blend: Blend,
+    },
+    Circle {
+        circle: Circle,
+        color: Color,
+        blend: Blend,
+    },
+    Ellipse {
+        ellipse: Ellipse,
+        color: Color,
+        blend: Blend,
+    },
+    Arc {
+        arc: Arc,
+        color: Color,
+        blend: Blend,
+    },
+    Polygon {
+        polygon: Polygon,
+        color: Color,
+        blend: Blend,
+    },
+    Text {
+        text: Text,
+        color: Color,
+        blend: Blend,
     },
 }
 
@@ -29,20 +62,90 @@ impl Graphic {
         Graphic::None
     }
     pub fn background(color: Color) -> Self {
-        Graphic::Background { color }
+        Graphic::Background {
+            color,
+            blend: Blend::Normal,
+        }
     }
     pub fn point(point: Point, color: Color) -> Self {
-        Graphic::Point { point, color }
+        Graphic::Point {
+            point,
+            color,
+            blend: Blend::Normal,
+        }
     }
     pub fn line(line: Line, color: Color) -> Self {
-        Graphic::Line { line, color }
+        Graphic::Line {
+            line,
+            color,
+            blend: Blend::Normal,
+            stroke: Stroke::Aliased,
+        }
     }
     pub fn rectangle(rectangle: Rectangle, color: Color) -> Self {
-        Graphic::Rectangle { rectangle, color }
+        Graphic::Rectangle {
+            rectangle,
+            color,
+            blend: Blend::Normal,
+        }
+    }
+    pub fn circle(circle: Circle, color: Color) -> Self {
+        Graphic::Circle {
+            circle,
+            color,
+            blend: Blend::Normal,
+        }
+    }
+    pub fn ellipse(ellipse: Ellipse, color: Color) -> Self {
+        Graphic::Ellipse {
+            ellipse,
+            color,
+            blend: Blend::Normal,
+        }
+    }
+    pub fn arc(arc: Arc, color: Color) -> Self {
+        Graphic::Arc {
+            arc,
+            color,
+            blend: Blend::Normal,
+        }
+    }
+    pub fn polygon(polygon: Polygon, color: Color) -> Self {
+        Graphic::Polygon {
+            polygon,
+            color,
+            blend: Blend::Normal,
+        }
+    }
+    pub fn text(text: Text, color: Color) -> Self {
+        Graphic::Text {
+            text,
+            color,
+            blend: Blend::Normal,
+        }
     }
 }
 
 impl Graphic {
+    pub fn set_blend(&mut self, value: Blend) {
+        match self {
+            Graphic::Background { blend, .. }
+            | Graphic::Point { blend, .. }
+            | Graphic::Line { blend, .. }
+            | Graphic::Rectangle { blend, .. }
+            | Graphic::Circle { blend, .. }
+            | Graphic::Ellipse { blend, .. }
+            | Graphic::Arc { blend, .. }
+            | Graphic::Polygon { blend, .. }
+            | Graphic::Text { blend, .. } => *blend = value,
+            _ => {}
+        }
+    }
+    pub fn set_stroke(&mut self, value: Stroke) {
+        if let Graphic::Line { stroke, .. } = self {
+            *stroke = value;
+        }
+    }
     pub fn set_x_y(&mut self, x: f32, y: f32) {
         match self {
             Graphic::Point { point, .. } => {
@@ -61,6 +164,27 @@ impl Graphic {
                 rectangle.x = x;
                 rectangle.y = y;
             }
+            Graphic::Circle { circle, .. } => {
+                circle.x = x;
+                circle.y = y;
+            }
+            Graphic::Ellipse { ellipse, .. } => {
+                ellipse.x = x;
+                ellipse.y = y;
+            }
+            Graphic::Arc { arc, .. } => {
+                arc.x = x;
+                arc.y = y;
+            }
+            Graphic::Polygon { polygon, .. } => {
+                if let Some(first) = polygon.points.first().copied() {
+                    polygon.translate(x - first.x, y - first.y);
+                }
+            }
+            Graphic::Text { text, .. } => {
+                text.x = x;
+                text.y = y;
+            }
             _ => {}
         }
     }
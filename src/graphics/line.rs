@@ -1,6 +1,14 @@
 use super::point::Point;
 
-#[derive(Debug, Copy, Clone, Default)]
+// Line rasterization
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum Stroke {
+    #[default]
+    Aliased, // Integer Bresenham
+    Smooth, // Xiaolin Wu, anti-aliased
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct Line {
     pub x0: f32,
     pub y0: f32,
@@ -19,4 +27,103 @@ impl Line {
         let margin = 0.5;
         a.distance_point(c) + b.distance_point(c) - a.distance_point(b) < margin
     }
+    pub fn length(&self) -> f32 {
+        Point::new(self.x0, self.y0).distance(self.x1, self.y1)
+    }
+    // Clip to a rectangle with Liang-Barsky, keeping the direction and slope of the line
+    pub fn clip(&self, left: f32, top: f32, right: f32, bottom: f32) -> Option<Line> {
+        let dx = self.x1 - self.x0;
+        let dy = self.y1 - self.y0;
+
+        // Entry and exit positions along the line, 0..1
+        let mut enter: f32 = 0.0;
+        let mut exit: f32 = 1.0;
+
+        let edges = [
+            (-dx, self.x0 - left),
+            (dx, right - self.x0),
+            (-dy, self.y0 - top),
+            (dy, bottom - self.y0),
+        ];
+
+        for (p, q) in edges {
+            if p == 0.0 {
+                // Parallel to the edge, inside or out for its whole length
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                enter = enter.max(q / p);
+            } else {
+                exit = exit.min(q / p);
+            }
+        }
+
+        if enter > exit {
+            return None;
+        }
+
+        // Endpoints inside stay exact
+        let (x0, y0) = if enter > 0.0 {
+            (self.x0 + dx * enter, self.y0 + dy * enter)
+        } else {
+            (self.x0, self.y0)
+        };
+
+        let (x1, y1) = if exit < 1.0 {
+            (self.x0 + dx * exit, self.y0 + dy * exit)
+        } else {
+            (self.x1, self.y1)
+        };
+
+        Some(Line::new(x0, y0, x1, y1))
+    }
+    // Intersect ray from origin along direction, returning ray distance and segment position 0..1
+    pub fn intersect(
+        &self,
+        x: f32,
+        y: f32,
+        direction_x: f32,
+        direction_y: f32,
+    ) -> Option<(f32, f32)> {
+        let segment_x = self.x1 - self.x0;
+        let segment_y = self.y1 - self.y0;
+
+        let denominator = direction_x * segment_y - direction_y * segment_x;
+
+        if denominator == 0.0 {
+            return None;
+        }
+
+        let offset_x = self.x0 - x;
+        let offset_y = self.y0 - y;
+
+        let distance = (offset_x * segment_y - offset_y * segment_x) / denominator;
+        let position = (offset_x * direction_y - offset_y * direction_x) / denominator;
+
+        if distance < 0.0 || !(0.0..=1.0).contains(&position) {
+            return None;
+        }
+
+        Some((distance, position))
+    }
+    // Shortest distance from point to segment
+    pub fn distance(&self, x: f32, y: f32) -> f32 {
+        let segment_x = self.x1 - self.x0;
+        let segment_y = self.y1 - self.y0;
+
+        let length = segment_x * segment_x + segment_y * segment_y;
+
+        let position = if length > 0.0 {
+            (((x - self.x0) * segment_x + (y - self.y0) * segment_y) / length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        Point::new(
+            self.x0 + segment_x * position,
+            self.y0 + segment_y * position,
+        )
+        .distance(x, y)
+    }
 }
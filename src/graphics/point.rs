@@ -17,4 +17,7 @@ impl Point {
     pub fn distance_point(&self, point: Point) -> f32 {
         self.distance(point.x, point.y)
     }
+    pub fn scale(&self, factor: f32) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
 }
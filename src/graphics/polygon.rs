@@ -0,0 +1,79 @@
+use super::line::Line;
+use super::point::Point;
+
+// Rule deciding which points a self-intersecting polygon covers
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum Fill {
+    #[default]
+    EvenOdd, // Inside when crossing an odd number of edges
+    NonZero, // Inside when the edges wind around the point
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Polygon {
+    pub points: Vec<Point>,
+    pub fill: Fill,
+    pub solid: bool,
+}
+
+impl Polygon {
+    pub fn new(points: Vec<Point>, fill: Fill, solid: bool) -> Self {
+        Self {
+            points,
+            fill,
+            solid,
+        }
+    }
+    pub fn triangle(a: Point, b: Point, c: Point, solid: bool) -> Self {
+        Self::new(vec![a, b, c], Fill::EvenOdd, solid)
+    }
+    // Edges between consecutive points, closed from the last point back to the first
+    pub fn edges(&self) -> impl Iterator<Item = Line> + '_ {
+        let count = self.points.len();
+
+        (0..if count > 1 { count } else { 0 }).map(move |index| {
+            let a = self.points[index];
+            let b = self.points[(index + 1) % count];
+            Line::new(a.x, a.y, b.x, b.y)
+        })
+    }
+    // Sum of edge directions crossing a horizontal ray from the point towards +x
+    pub fn winding(&self, x: f32, y: f32) -> i32 {
+        self.edges()
+            .filter_map(|edge| crossing(&edge, y))
+            .filter(|(crossing, _)| *crossing > x)
+            .map(|(_, direction)| direction)
+            .sum()
+    }
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let winding = self.winding(x, y);
+
+        match self.fill {
+            Fill::EvenOdd => winding % 2 != 0,
+            Fill::NonZero => winding != 0,
+        }
+    }
+    pub fn translate(&mut self, x: f32, y: f32) {
+        for point in self.points.iter_mut() {
+            point.x += x;
+            point.y += y;
+        }
+    }
+}
+
+// Position along a row where an edge crosses it, with 1 for downward and -1 for upward edges
+//
+// Edges include their upper end only, so rows through a shared vertex count it once.
+pub fn crossing(edge: &Line, y: f32) -> Option<(f32, i32)> {
+    let direction = if edge.y0 <= y && y < edge.y1 {
+        1
+    } else if edge.y1 <= y && y < edge.y0 {
+        -1
+    } else {
+        return None;
+    };
+
+    let position = (y - edge.y0) / (edge.y1 - edge.y0);
+
+    Some((edge.x0 + (edge.x1 - edge.x0) * position, direction))
+}
@@ -0,0 +1,80 @@
+use super::font::{GLYPH_HEIGHT, GLYPH_WIDTH, LEADING, SPACING};
+use super::rectangle::Rectangle;
+
+// Horizontal alignment of each line relative to the text position
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Text {
+    pub x: f32,
+    pub y: f32,
+    pub content: String,
+    pub scale: u32, // Screen pixels per font pixel
+    pub align: Align,
+}
+
+impl Text {
+    pub fn new(x: f32, y: f32, content: impl Into<String>, scale: u32, align: Align) -> Self {
+        Self {
+            x,
+            y,
+            content: content.into(),
+            scale,
+            align,
+        }
+    }
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.content.split('\n')
+    }
+    // Distance between the tops of consecutive lines
+    pub fn line_height(&self) -> f32 {
+        ((GLYPH_HEIGHT + LEADING) * self.scale) as f32
+    }
+    // Distance between the left edges of consecutive glyphs
+    pub fn advance(&self) -> f32 {
+        ((GLYPH_WIDTH + SPACING) * self.scale) as f32
+    }
+    pub fn measure(&self, line: &str) -> f32 {
+        let count = line.chars().count() as f32;
+        (count * self.advance() - (SPACING * self.scale) as f32).max(0.0)
+    }
+    // Width of the widest line and height of all lines
+    pub fn size(&self) -> (f32, f32) {
+        let width = self
+            .lines()
+            .map(|line| self.measure(line))
+            .fold(0.0, f32::max);
+        let lines = self.lines().count() as f32;
+        let height = lines * self.line_height() - (LEADING * self.scale) as f32;
+
+        (width, height)
+    }
+    // Left edge of a line after alignment
+    pub fn left(&self, line: &str) -> f32 {
+        match self.align {
+            Align::Left => self.x,
+            Align::Center => self.x - (self.measure(line) / 2.0).round(),
+            Align::Right => self.x - self.measure(line),
+        }
+    }
+    pub fn bounds(&self) -> Rectangle {
+        let (width, height) = self.size();
+
+        let left = match self.align {
+            Align::Left => self.x,
+            Align::Center => self.x - (width / 2.0).round(),
+            Align::Right => self.x - width,
+        };
+
+        Rectangle::new(left, self.y, width, height, true)
+    }
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        self.bounds().contains(x, y)
+    }
+}
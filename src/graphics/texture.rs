@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use image::ImageResult;
+
+use super::color::Color;
+
+#[derive(Debug, Clone, Default)]
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl Texture {
+    pub fn new(width: u32, height: u32, data: Vec<u8>) -> Self {
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+    pub fn load(path: impl AsRef<Path>) -> ImageResult<Self> {
+        let image = image::open(path)?.to_rgba8();
+        Ok(Self::new(image.width(), image.height(), image.into_raw()))
+    }
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        let index = ((x + y * self.width) * 4) as usize;
+        Color::new(
+            self.data[index],
+            self.data[index + 1],
+            self.data[index + 2],
+            self.data[index + 3],
+        )
+    }
+    // Sample texel at normalized coordinates, wrapping around the edges
+    pub fn sample(&self, u: f32, v: f32) -> Color {
+        let x = (u.rem_euclid(1.0) * self.width as f32) as u32;
+        let y = (v.rem_euclid(1.0) * self.height as f32) as u32;
+        self.pixel(x.min(self.width - 1), y.min(self.height - 1))
+    }
+}
@@ -0,0 +1,6 @@
+pub mod backend;
+pub mod controls;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod keyboard;
+pub mod script;
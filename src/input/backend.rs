@@ -0,0 +1,38 @@
+use super::controls::{Action, Controls};
+use super::keyboard::Keyboard;
+
+// Source of action values polled once per update
+pub trait Backend {
+    fn poll(&mut self, time: f32) -> Vec<(Action, f32)>;
+}
+
+// Input layer combining keyboard and mouse bindings with polled backends
+pub struct Input {
+    pub keyboard: Keyboard,              // Keyboard and mouse bindings
+    pub backends: Vec<Box<dyn Backend>>, // Polled backends
+}
+
+impl Input {
+    pub fn new(controls: Controls) -> Self {
+        Self {
+            keyboard: Keyboard::new(controls),
+            backends: Vec::new(),
+        }
+    }
+    pub fn attach(&mut self, backend: impl Backend + 'static) {
+        self.backends.push(Box::new(backend));
+    }
+    // Poll all backends in attachment order
+    pub fn poll(&mut self, time: f32) -> Vec<(Action, f32)> {
+        self.backends
+            .iter_mut()
+            .flat_map(|backend| backend.poll(time))
+            .collect()
+    }
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new(Controls::new())
+    }
+}
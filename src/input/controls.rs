@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+// Named player action, held actions take values from 0 to 1
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Forward,
+    Backward,
+    StrafeLeft,
+    StrafeRight,
+    TurnLeft,
+    TurnRight,
+    Use,
+    LookUp,
+    LookDown,
+    Jump,
+    Crouch,
+    #[serde(skip)]
+    Look, // Relative yaw in radians, not bindable to buttons
+    #[serde(skip)]
+    Pitch, // Relative pitch in radians, not bindable to buttons
+}
+
+// Controls loading error
+#[derive(Debug)]
+pub enum ControlsError {
+    Io(io::Error),
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for ControlsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ControlsError::Io(error) => write!(f, "{error}"),
+            ControlsError::Syntax {
+                line,
+                column,
+                message,
+            } => write!(f, "{line}:{column}: {message}"),
+        }
+    }
+}
+
+impl Error for ControlsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ControlsError::Io(error) => Some(error),
+            ControlsError::Syntax { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ControlsError {
+    fn from(error: io::Error) -> Self {
+        ControlsError::Io(error)
+    }
+}
+
+// Keybinding table, mapping physical key and mouse button names to actions
+#[derive(Debug, Clone)]
+pub struct Controls {
+    pub bindings: HashMap<String, Action>, // Button name to action
+    pub sensitivity: f32,                  // Mouse yaw and pitch in radians per count
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Controls {
+    // Default bindings, ESDF layout with strafing on W and R
+    pub fn new() -> Self {
+        let bindings = [
+            ("KeyE", Action::Forward),
+            ("KeyD", Action::Backward),
+            ("KeyW", Action::StrafeLeft),
+            ("KeyR", Action::StrafeRight),
+            ("KeyS", Action::TurnLeft),
+            ("KeyF", Action::TurnRight),
+            ("ArrowUp", Action::Forward),
+            ("ArrowDown", Action::Backward),
+            ("ArrowLeft", Action::TurnLeft),
+            ("ArrowRight", Action::TurnRight),
+            ("Space", Action::Use),
+            ("PageUp", Action::LookUp),
+            ("PageDown", Action::LookDown),
+            ("KeyA", Action::Jump),
+            ("KeyZ", Action::Crouch),
+        ];
+
+        Self {
+            bindings: bindings
+                .into_iter()
+                .map(|(button, action)| (button.to_string(), action))
+                .collect(),
+            sensitivity: 0.003,
+        }
+    }
+
+    // Load controls from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ControlsError> {
+        let source = fs::read_to_string(path)?;
+        Self::parse(&source)
+    }
+
+    // Parse controls from JSON, listing buttons per action
+    pub fn parse(source: &str) -> Result<Self, ControlsError> {
+        let file: ControlsFile =
+            serde_json::from_str(source).map_err(|error| ControlsError::Syntax {
+                line: error.line(),
+                column: error.column(),
+                message: error.to_string(),
+            })?;
+
+        let mut bindings = HashMap::new();
+
+        for (action, buttons) in file.bindings {
+            for button in buttons {
+                if let Some(previous) = bindings.insert(button.clone(), action) {
+                    let (line, column) = locate(source, &button);
+
+                    return Err(ControlsError::Syntax {
+                        line,
+                        column,
+                        message: format!(
+                            "button `{button}` bound to both {previous:?} and {action:?}"
+                        ),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            bindings,
+            sensitivity: file.sensitivity,
+        })
+    }
+
+    // Look up action bound to a button
+    pub fn action(&self, button: &str) -> Option<Action> {
+        self.bindings.get(button).copied()
+    }
+}
+
+// Find last line and column of a quoted string in source
+fn locate(source: &str, text: &str) -> (usize, usize) {
+    let quoted = format!("\"{text}\"");
+
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| line.find(&quoted).map(|column| (index + 1, column + 1)))
+        .last()
+        .unwrap_or((1, 1))
+}
+
+// JSON controls file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ControlsFile {
+    #[serde(default = "default_sensitivity")]
+    sensitivity: f32,
+    bindings: BTreeMap<Action, Vec<String>>,
+}
+
+fn default_sensitivity() -> f32 {
+    Controls::new().sensitivity
+}
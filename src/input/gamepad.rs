@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use gilrs::{Axis, Button, Gilrs};
+
+use super::backend::Backend;
+use super::controls::Action;
+
+// Stick values below this magnitude are ignored
+const DEADZONE: f32 = 0.15;
+
+// Gamepad backend, left stick moves and strafes, right stick turns and looks up or down,
+// south button uses, north button jumps and east button crouches
+pub struct Gamepad {
+    gilrs: Gilrs,
+    values: HashMap<Action, f32>, // Last emitted values
+}
+
+impl Gamepad {
+    pub fn new() -> Result<Self, gilrs::Error> {
+        Ok(Self {
+            gilrs: Gilrs::new()?,
+            values: HashMap::new(),
+        })
+    }
+}
+
+// Remove deadzone and rescale remaining range to 0..1
+fn deadzone(value: f32) -> f32 {
+    if value.abs() < DEADZONE {
+        return 0.0;
+    }
+
+    (value.abs() - DEADZONE) / (1.0 - DEADZONE) * value.signum()
+}
+
+impl Backend for Gamepad {
+    // Emit changed stick values only, so idle sticks don't override the keyboard
+    fn poll(&mut self, _time: f32) -> Vec<(Action, f32)> {
+        while self.gilrs.next_event().is_some() {}
+
+        let (forward, strafe, turn, look) = match self.gilrs.gamepads().next() {
+            Some((_, gamepad)) => (
+                deadzone(gamepad.value(Axis::LeftStickY)),
+                deadzone(gamepad.value(Axis::LeftStickX)),
+                deadzone(gamepad.value(Axis::RightStickX)),
+                deadzone(gamepad.value(Axis::RightStickY)),
+            ),
+            None => (0.0, 0.0, 0.0, 0.0),
+        };
+
+        let button = |button: Button| {
+            let pressed = self
+                .gilrs
+                .gamepads()
+                .next()
+                .is_some_and(|(_, gamepad)| gamepad.is_pressed(button));
+
+            if pressed {
+                1.0
+            } else {
+                0.0
+            }
+        };
+
+        let actions = [
+            (Action::Forward, forward.max(0.0)),
+            (Action::Backward, (-forward).max(0.0)),
+            (Action::StrafeRight, strafe.max(0.0)),
+            (Action::StrafeLeft, (-strafe).max(0.0)),
+            (Action::TurnRight, turn.max(0.0)),
+            (Action::TurnLeft, (-turn).max(0.0)),
+            (Action::LookUp, look.max(0.0)),
+            (Action::LookDown, (-look).max(0.0)),
+            (Action::Use, button(Button::South)),
+            (Action::Jump, button(Button::North)),
+            (Action::Crouch, button(Button::East)),
+        ];
+
+        actions
+            .into_iter()
+            .filter(|(action, value)| self.values.insert(*action, *value).unwrap_or(0.0) != *value)
+            .collect()
+    }
+}
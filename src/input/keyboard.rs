@@ -0,0 +1,42 @@
+use std::collections::HashSet;
+
+use super::controls::{Action, Controls};
+
+// Keyboard and mouse backend, resolving button events through the keybinding table
+pub struct Keyboard {
+    pub controls: Controls,   // Keybinding table
+    pressed: HashSet<String>, // Buttons pressed
+}
+
+impl Keyboard {
+    pub fn new(controls: Controls) -> Self {
+        Self {
+            controls,
+            pressed: HashSet::new(),
+        }
+    }
+    // Handle button press or release, returning the new value of its bound action
+    pub fn button(&mut self, name: &str, active: bool) -> Option<(Action, f32)> {
+        if active {
+            self.pressed.insert(name.into());
+        } else {
+            self.pressed.remove(name);
+        }
+
+        let action = self.controls.action(name)?;
+
+        let held = self
+            .pressed
+            .iter()
+            .any(|button| self.controls.action(button) == Some(action));
+
+        Some((action, if held { 1.0 } else { 0.0 }))
+    }
+    // Convert relative mouse motion into look actions, moving the mouse up looks up
+    pub fn motion(&self, x: f32, y: f32) -> [(Action, f32); 2] {
+        [
+            (Action::Look, x * self.controls.sensitivity),
+            (Action::Pitch, -y * self.controls.sensitivity),
+        ]
+    }
+}
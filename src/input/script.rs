@@ -0,0 +1,53 @@
+use super::backend::Backend;
+use super::controls::Action;
+
+// Timed action value
+#[derive(Debug, Copy, Clone)]
+pub struct ScriptEvent {
+    pub time: f32,      // Time in seconds
+    pub action: Action, // Action
+    pub value: f32,     // Action value
+}
+
+impl ScriptEvent {
+    pub fn new(time: f32, action: Action, value: f32) -> Self {
+        Self {
+            time,
+            action,
+            value,
+        }
+    }
+}
+
+// Synthetic backend replaying timed action values, for tests and demos
+pub struct Script {
+    events: Vec<ScriptEvent>,
+    index: usize,
+}
+
+impl Script {
+    pub fn new(events: Vec<ScriptEvent>) -> Self {
+        let mut events = events;
+        events.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        Self { events, index: 0 }
+    }
+    pub fn finished(&self) -> bool {
+        self.index >= self.events.len()
+    }
+}
+
+impl Backend for Script {
+    // Emit all events due up to time
+    fn poll(&mut self, time: f32) -> Vec<(Action, f32)> {
+        let due = self.events[self.index..]
+            .iter()
+            .take_while(|event| event.time <= time)
+            .map(|event| (event.action, event.value))
+            .collect::<Vec<_>>();
+
+        self.index += due.len();
+
+        due
+    }
+}
@@ -2,3 +2,4 @@ pub mod app;
 pub mod canvas;
 pub mod display;
 pub mod graphics;
+pub mod input;
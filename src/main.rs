@@ -1,9 +1,87 @@
-use ray_casting_prototype::display::run;
+use std::env;
+use std::process;
 
-use ray_casting_prototype::app::data::{FILTER, HEIGHT, RESIZABLE, SCALE, TITLE, WIDTH};
-use ray_casting_prototype::app::state::State;
+use ray_casting_prototype::display::window::{WindowConfiguration, WindowOptions};
+use ray_casting_prototype::display::{headless, run};
+use ray_casting_prototype::input::backend::Input;
+use ray_casting_prototype::input::controls::Controls;
+
+use ray_casting_prototype::app::data::{FILTER, HEIGHT, RESIZABLE, SCALE, TIMESTEP, TITLE, WIDTH};
+use ray_casting_prototype::app::state::{Projection, State};
 
 fn main() {
-    let state = State::build();
-    run(WIDTH, HEIGHT, SCALE, RESIZABLE, FILTER, TITLE.into(), state);
+    let args: Vec<String> = env::args().collect();
+
+    let option = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .map(|index| args.get(index + 1).map_or("", |value| value.as_str()))
+    };
+
+    let mut state = match option("--map") {
+        Some(path) => State::load(path).unwrap_or_else(|error| {
+            eprintln!("{path}:{error}");
+            process::exit(1);
+        }),
+        None => State::build(),
+    };
+
+    state.conf.projection = match option("--projection") {
+        Some("angular") | None => Projection::Angular,
+        Some("plane") => Projection::CameraPlane,
+        Some(name) => {
+            eprintln!("--projection: unknown projection `{name}`, expected `angular` or `plane`");
+            process::exit(1);
+        }
+    };
+
+    if let Some(path) = option("--screenshot") {
+        let path = if path.is_empty() {
+            "screenshot.png"
+        } else {
+            path
+        };
+
+        headless::run(WIDTH, HEIGHT, 1, 1.0 / 60.0, path, &mut state)
+            .expect("Headless frame should be saved as image");
+
+        return;
+    }
+
+    let timestep = match option("--timestep") {
+        Some(rate) => {
+            let rate = if rate.is_empty() { "60" } else { rate };
+
+            match rate.parse::<f32>() {
+                Ok(rate) if rate > 0.0 => Some(1.0 / rate),
+                _ => {
+                    eprintln!("--timestep: invalid update rate `{rate}`");
+                    process::exit(1);
+                }
+            }
+        }
+        None => TIMESTEP,
+    };
+
+    let controls = match option("--controls") {
+        Some(path) => Controls::load(path).unwrap_or_else(|error| {
+            eprintln!("{path}:{error}");
+            process::exit(1);
+        }),
+        None => Controls::new(),
+    };
+
+    #[allow(unused_mut)]
+    let mut input = Input::new(controls);
+
+    #[cfg(feature = "gamepad")]
+    match ray_casting_prototype::input::gamepad::Gamepad::new() {
+        Ok(gamepad) => input.attach(gamepad),
+        Err(error) => eprintln!("gamepad: {error}"),
+    }
+
+    let configuration =
+        WindowConfiguration::new(WIDTH, HEIGHT, SCALE, RESIZABLE, FILTER, TITLE.into());
+
+    run(configuration, WindowOptions::new(timestep, input), state);
 }
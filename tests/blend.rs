@@ -0,0 +1,130 @@
+mod common;
+
+use common::{channels, filled, pixel};
+
+use ray_casting_prototype::canvas;
+use ray_casting_prototype::graphics::color::{Blend, Color};
+use ray_casting_prototype::graphics::graphic::Graphic;
+use ray_casting_prototype::graphics::rectangle::Rectangle;
+
+const WIDTH: u32 = 8;
+const HEIGHT: u32 = 8;
+
+#[test]
+fn blend_modes() {
+    let destination = Color::new(200, 100, 50, 255);
+    let half = Color::new(100, 200, 250, 128);
+
+    // Opaque source over replaces, transparent source keeps the destination
+    let opaque = Color::new(1, 2, 3, 255);
+    assert_eq!(
+        channels(opaque.blend(destination, Blend::Normal)),
+        [1, 2, 3, 255]
+    );
+
+    let clear = Color::new(1, 2, 3, 0);
+    assert_eq!(
+        channels(clear.blend(destination, Blend::Normal)),
+        [200, 100, 50, 255]
+    );
+    assert_eq!(
+        channels(clear.blend(destination, Blend::Replace)),
+        [1, 2, 3, 0]
+    );
+
+    assert_eq!(
+        channels(half.blend(destination, Blend::Normal)),
+        [150, 150, 150, 255]
+    );
+    assert_eq!(
+        channels(half.blend(destination, Blend::Add)),
+        [250, 200, 175, 255]
+    );
+    assert_eq!(
+        channels(half.blend(destination, Blend::Multiply)),
+        [139, 89, 50, 255]
+    );
+}
+
+#[test]
+fn blend_over_transparent() {
+    let empty = Color::new(0, 0, 0, 0);
+    let half = Color::new(100, 200, 250, 128);
+
+    // Nothing to blend with, the source keeps its color and alpha
+    for blend in [Blend::Normal, Blend::Add, Blend::Multiply] {
+        assert_eq!(channels(half.blend(empty, blend)), [100, 200, 250, 128]);
+    }
+
+    let quarter = Color::new(0, 0, 0, 64);
+    let over = half.blend(quarter, Blend::Normal);
+
+    assert_eq!(channels(over), [80, 160, 200, 160]);
+}
+
+#[test]
+fn translucent_rectangle() {
+    let mut buffer = filled(WIDTH, HEIGHT, Color::new(0, 0, 0, 255));
+
+    let glass = Color::new(255, 255, 255, 64);
+    let rectangle = Rectangle::new(2.0, 2.0, 4.0, 4.0, true);
+
+    canvas::rectangle::render(
+        &mut buffer,
+        WIDTH,
+        HEIGHT,
+        &rectangle,
+        &glass,
+        Blend::Normal,
+    );
+    assert_eq!(pixel(&buffer, WIDTH, 3, 3), [64, 64, 64, 255]);
+    assert_eq!(pixel(&buffer, WIDTH, 1, 1), [0, 0, 0, 255]);
+
+    // Layers accumulate
+    canvas::rectangle::render(
+        &mut buffer,
+        WIDTH,
+        HEIGHT,
+        &rectangle,
+        &glass,
+        Blend::Normal,
+    );
+    assert_eq!(pixel(&buffer, WIDTH, 3, 3), [112, 112, 112, 255]);
+}
+
+#[test]
+fn outline_blends_each_pixel_once() {
+    let mut buffer = filled(WIDTH, HEIGHT, Color::new(0, 0, 0, 255));
+
+    let color = Color::new(255, 0, 0, 128);
+    let rectangle = Rectangle::new(1.0, 1.0, 5.0, 5.0, false);
+
+    canvas::rectangle::render_outline(&mut buffer, WIDTH, HEIGHT, &rectangle, &color, Blend::Add);
+
+    for (x, y) in [(1, 1), (6, 1), (1, 6), (3, 1), (1, 3), (6, 5), (5, 6)] {
+        assert_eq!(
+            pixel(&buffer, WIDTH, x, y),
+            [128, 0, 0, 255],
+            "pixel {x} {y}"
+        );
+    }
+
+    assert_eq!(pixel(&buffer, WIDTH, 3, 3), [0, 0, 0, 255]);
+}
+
+#[test]
+fn graphic_blend() {
+    let mut buffer = filled(WIDTH, HEIGHT, Color::new(100, 100, 100, 255));
+
+    let rectangle = Rectangle::new(0.0, 0.0, 2.0, 2.0, true);
+    let mut shadow = Graphic::rectangle(rectangle, Color::new(128, 128, 128, 255));
+    shadow.set_blend(Blend::Multiply);
+
+    let mut light = Graphic::background(Color::new(20, 20, 20, 255));
+    light.set_blend(Blend::Add);
+
+    canvas::graphics::render(&mut buffer, WIDTH, HEIGHT, &vec![&shadow, &light]);
+
+    assert_eq!(pixel(&buffer, WIDTH, 0, 0), [70, 70, 70, 255]);
+    assert_eq!(pixel(&buffer, WIDTH, 4, 4), [120, 120, 120, 255]);
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use common::{black, count, red, RED};
+
+use ray_casting_prototype::canvas;
+use ray_casting_prototype::graphics::circle::{Arc, Circle, Ellipse};
+use ray_casting_prototype::graphics::color::{Blend, Color};
+use ray_casting_prototype::graphics::graphic::Graphic;
+
+const WIDTH: u32 = 24;
+const HEIGHT: u32 = 20;
+
+#[test]
+fn contains() {
+    let circle = Circle::new(10.0, 10.0, 5.0, true);
+    assert!(circle.contains(10.0, 10.0));
+    assert!(circle.contains(15.0, 10.0));
+    assert!(!circle.contains(14.0, 14.0));
+
+    let ellipse = Ellipse::new(10.0, 10.0, 8.0, 2.0, true);
+    assert!(ellipse.contains(17.5, 10.0));
+    assert!(!ellipse.contains(10.0, 12.5));
+    assert!(!Ellipse::new(0.0, 0.0, 0.0, 4.0, true).contains(0.0, 0.0));
+
+    let arc = Arc::new(0.0, 0.0, 10.0, 0.0, PI / 2.0);
+    assert!(arc.contains(10.0, 0.2));
+    assert!(arc.contains(0.0, 10.0));
+    assert!(!arc.contains(-10.0, 0.0));
+    assert!(!arc.contains(5.0, 5.0));
+
+    // Sweeping backwards from the start
+    let arc = Arc::new(0.0, 0.0, 10.0, 0.0, -PI / 2.0);
+    assert!(arc.contains(0.0, -10.0));
+    assert!(!arc.contains(0.0, 10.0));
+}
+
+#[test]
+fn filled_circle() {
+    let mut buffer = black(WIDTH, HEIGHT);
+    let circle = Circle::new(12.0, 10.0, 6.0, true);
+    canvas::circle::render(&mut buffer, WIDTH, HEIGHT, &circle, &RED, Blend::Normal);
+
+    // Pixels are filled where their centers fall inside
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let inside = circle.contains(x as f32 + 0.5, y as f32 + 0.5);
+            assert_eq!(red(&buffer, WIDTH, x, y) > 0, inside, "pixel {x} {y}");
+        }
+    }
+
+    // Area close to that of the circle
+    let area = count(&buffer, WIDTH, HEIGHT);
+    assert!((area as f32 - PI * 36.0).abs() < 8.0, "area {area}");
+}
+
+#[test]
+fn filled_ellipse_clipped() {
+    let mut buffer = black(WIDTH, HEIGHT);
+    let ellipse = Ellipse::new(2.0, 18.0, 10.0, 5.0, true);
+    canvas::circle::render_ellipse(&mut buffer, WIDTH, HEIGHT, &ellipse, &RED, Blend::Normal);
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let inside = ellipse.contains(x as f32 + 0.5, y as f32 + 0.5);
+            assert_eq!(red(&buffer, WIDTH, x, y) > 0, inside, "pixel {x} {y}");
+        }
+    }
+}
+
+#[test]
+fn outline_is_a_closed_ring() {
+    let mut buffer = black(WIDTH, HEIGHT);
+    let circle = Circle::new(12.0, 10.0, 7.0, false);
+    let color = Color::new(255, 0, 0, 128);
+    canvas::circle::render_outline(&mut buffer, WIDTH, HEIGHT, &circle, &color, Blend::Add);
+
+    // Each ring pixel blended once, the inside left empty
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let value = red(&buffer, WIDTH, x, y);
+            assert!(value == 0 || value == 128, "pixel {x} {y} blended {value}");
+        }
+    }
+
+    assert_eq!(red(&buffer, WIDTH, 12, 10), 0);
+
+    // Every row and column crossing the circle hits the ring on both sides
+    for row in 4..16 {
+        let lit: Vec<u32> = (0..WIDTH)
+            .filter(|x| red(&buffer, WIDTH, *x, row) > 0)
+            .collect();
+        assert!(
+            lit.first() < Some(&12) && lit.last() > Some(&12),
+            "row {row}"
+        );
+    }
+
+    for column in 6..18 {
+        let lit: Vec<u32> = (0..HEIGHT)
+            .filter(|y| red(&buffer, WIDTH, column, *y) > 0)
+            .collect();
+        assert!(
+            lit.first() < Some(&10) && lit.last() > Some(&10),
+            "column {column}"
+        );
+    }
+}
+
+#[test]
+fn arc_quadrant() {
+    let mut buffer = black(WIDTH, HEIGHT);
+    let arc = Arc::new(12.0, 10.0, 6.0, 0.0, PI / 2.0);
+    canvas::circle::render_arc(&mut buffer, WIDTH, HEIGHT, &arc, &RED, Blend::Normal);
+
+    // Lower right quadrant only, y pointing down
+    assert!(red(&buffer, WIDTH, 17, 10) > 0);
+    assert!(red(&buffer, WIDTH, 12, 15) > 0);
+    assert_eq!(red(&buffer, WIDTH, 6, 10), 0);
+    assert_eq!(red(&buffer, WIDTH, 12, 4), 0);
+
+    let mut full = black(WIDTH, HEIGHT);
+    let circle = Arc::new(12.0, 10.0, 6.0, 0.0, PI * 2.0);
+    canvas::circle::render_arc(&mut full, WIDTH, HEIGHT, &circle, &RED, Blend::Normal);
+
+    let quarter = count(&buffer, WIDTH, HEIGHT) as f32 / count(&full, WIDTH, HEIGHT) as f32;
+    assert!((quarter - 0.25).abs() < 0.05, "quarter {quarter}");
+}
+
+#[test]
+fn graphic_shapes() {
+    let mut disc = Graphic::circle(Circle::new(5.0, 5.0, 3.0, true), RED);
+    disc.set_x_y(18.0, 14.0);
+
+    let ellipse = Graphic::ellipse(Ellipse::new(5.0, 5.0, 4.0, 2.0, false), RED);
+    let arc = Graphic::arc(Arc::new(5.0, 5.0, 3.0, PI, PI * 1.5), RED);
+
+    let mut buffer = black(WIDTH, HEIGHT);
+    canvas::graphics::render(&mut buffer, WIDTH, HEIGHT, &vec![&disc, &ellipse, &arc]);
+
+    assert!(red(&buffer, WIDTH, 18, 14) > 0);
+    assert_eq!(red(&buffer, WIDTH, 5, 5), 0);
+    assert!(red(&buffer, WIDTH, 1, 5) > 0);
+}
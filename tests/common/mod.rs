@@ -0,0 +1,202 @@
+// Each test crate uses a subset of these helpers
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use image::{Rgba, RgbaImage};
+
+use ray_casting_prototype::app::level::{Level, LevelError};
+use ray_casting_prototype::app::logic::{render_graphics, update_state};
+use ray_casting_prototype::app::state::State;
+use ray_casting_prototype::canvas;
+use ray_casting_prototype::graphics::color::{Blend, Color};
+
+// Player pose
+#[derive(Debug, Clone, Copy)]
+pub struct Pose {
+    pub x: f32,
+    pub y: f32,
+    pub direction: f32,
+}
+
+impl Pose {
+    pub fn new(x: f32, y: f32, direction: f32) -> Self {
+        Self { x, y, direction }
+    }
+}
+
+// Render state at a fixed player pose into an RGBA buffer
+pub fn render_pose(state: &mut State, pose: Pose) -> RgbaImage {
+    let width = state.env.width;
+    let height = state.env.height;
+
+    state.sub.x = pose.x;
+    state.sub.y = pose.y;
+    state.sub.direction = pose.direction;
+    state.conf.overlay = false;
+
+    update_state(state, 0.0, 0.0, 0.0);
+
+    let mut buffer = vec![0u8; (width * height * 4) as usize];
+
+    render_graphics(state, &mut buffer, width, height);
+
+    RgbaImage::from_raw(width, height, buffer).expect("Frame buffer should match image dimensions")
+}
+
+pub const RED: Color = Color {
+    red: 255,
+    green: 0,
+    blue: 0,
+    alpha: 255,
+};
+
+pub const WHITE: Color = Color {
+    red: 255,
+    green: 255,
+    blue: 255,
+    alpha: 255,
+};
+
+// Canvas buffer filled with a color
+pub fn filled(width: u32, height: u32, color: Color) -> Vec<u8> {
+    let mut buffer = vec![0u8; (width * height * 4) as usize];
+    canvas::background::render(&mut buffer, &color, Blend::Replace);
+    buffer
+}
+
+// Opaque black canvas buffer
+pub fn black(width: u32, height: u32) -> Vec<u8> {
+    filled(width, height, Color::new(0, 0, 0, 255))
+}
+
+pub fn channels(color: Color) -> [u8; 4] {
+    [color.red, color.green, color.blue, color.alpha]
+}
+
+// Channels of a canvas pixel
+pub fn pixel(buffer: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
+    let index = ((x + y * width) * 4) as usize;
+    [
+        buffer[index],
+        buffer[index + 1],
+        buffer[index + 2],
+        buffer[index + 3],
+    ]
+}
+
+// Red channel of a canvas pixel
+pub fn red(buffer: &[u8], width: u32, x: u32, y: u32) -> u8 {
+    pixel(buffer, width, x, y)[0]
+}
+
+// Pixels with a red channel, row by row
+pub fn lit(buffer: &[u8], width: u32, height: u32) -> Vec<(u32, u32)> {
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|(x, y)| red(buffer, width, *x, *y) > 0)
+        .collect()
+}
+
+pub fn count(buffer: &[u8], width: u32, height: u32) -> usize {
+    lit(buffer, width, height).len()
+}
+
+// Line, column and message of a level syntax error
+pub fn syntax(result: Result<Level, LevelError>) -> (usize, usize, String) {
+    match result {
+        Err(LevelError::Syntax {
+            line,
+            column,
+            message,
+        }) => (line, column, message),
+        Err(error) => panic!("Expected syntax error, found {error}"),
+        Ok(_) => panic!("Expected syntax error, found level"),
+    }
+}
+
+fn reference_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{name}.png"))
+}
+
+fn output_path(name: &str, kind: &str) -> PathBuf {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("golden");
+    fs::create_dir_all(&directory).expect("Golden output directory should be created");
+    directory.join(format!("{name}.{kind}.png"))
+}
+
+// Compare image against reference, writing actual and diff images on failure
+//
+// Set `GOLDEN_UPDATE=1` to (re)write the reference images instead.
+pub fn assert_golden(name: &str, actual: &RgbaImage, tolerance: u8) {
+    let path = reference_path(name);
+
+    if env::var("GOLDEN_UPDATE").is_ok_and(|value| value == "1") {
+        actual.save(&path).expect("Reference image should be saved");
+        return;
+    }
+
+    let expected = image::open(&path)
+        .unwrap_or_else(|error| {
+            panic!(
+                "Reference image {} should load ({error}), run with GOLDEN_UPDATE=1 to create it",
+                path.display()
+            )
+        })
+        .to_rgba8();
+
+    assert_eq!(
+        expected.dimensions(),
+        actual.dimensions(),
+        "Image {name} dimensions differ from reference"
+    );
+
+    let (width, height) = expected.dimensions();
+    let mut diff = RgbaImage::new(width, height);
+    let mut mismatches = 0;
+    let mut largest = 0;
+
+    for (x, y, expected_pixel) in expected.enumerate_pixels() {
+        let actual_pixel = actual.get_pixel(x, y);
+
+        let difference = expected_pixel
+            .0
+            .iter()
+            .zip(actual_pixel.0.iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap_or(0);
+
+        largest = largest.max(difference);
+
+        if difference > tolerance {
+            mismatches += 1;
+            diff.put_pixel(x, y, Rgba([255, 0, 255, 255]));
+        } else {
+            let [red, green, blue, _] = expected_pixel.0;
+            let grey = ((red as u32 + green as u32 + blue as u32) / 12) as u8;
+            diff.put_pixel(x, y, Rgba([grey, grey, grey, 255]));
+        }
+    }
+
+    if mismatches > 0 {
+        let actual_path = output_path(name, "actual");
+        let diff_path = output_path(name, "diff");
+
+        actual
+            .save(&actual_path)
+            .expect("Actual image should be saved");
+        diff.save(&diff_path).expect("Diff image should be saved");
+
+        panic!(
+            "Image {name} differs from reference in {mismatches} pixels (largest channel difference {largest}, tolerance {tolerance})\n  actual: {}\n  diff: {}",
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
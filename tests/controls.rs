@@ -0,0 +1,151 @@
+use std::f32::consts::PI;
+
+use ray_casting_prototype::app::level::Level;
+use ray_casting_prototype::app::logic::update_state;
+use ray_casting_prototype::app::state::State;
+use ray_casting_prototype::display::window::Graphics;
+use ray_casting_prototype::input::controls::{Action, Controls, ControlsError};
+use ray_casting_prototype::input::keyboard::Keyboard;
+
+const DELTA: f32 = 1.0 / 60.0;
+
+const MAP: &str = "size 5 5
+unit 10
+spawn 2.5 2.5 0
+
+grid
+11111
+1...1
+1...1
+1...1
+11111
+";
+
+fn state() -> State {
+    let mut state = State::new();
+    state.apply(&Level::parse(MAP).unwrap());
+    state.init();
+    state
+}
+
+fn state_at(x: f32, y: f32) -> State {
+    let mut state = state();
+    state.sub.x = x;
+    state.sub.y = y;
+    state
+}
+
+#[test]
+fn load_default_file() {
+    let controls = Controls::load("config/controls.json").unwrap();
+
+    assert_eq!(controls.action("KeyW"), Some(Action::StrafeLeft));
+    assert_eq!(controls.action("MouseRight"), Some(Action::Forward));
+    assert_eq!(controls.action("KeyQ"), None);
+    assert_eq!(controls.sensitivity, 0.003);
+}
+
+#[test]
+fn parse_errors() {
+    let source = r#"{
+  "bindings": {
+    "forward": ["KeyE"],
+    "fly": ["Space"]
+  }
+}"#;
+
+    match Controls::parse(source) {
+        Err(ControlsError::Syntax { line, message, .. }) => {
+            assert_eq!(line, 4);
+            assert!(message.starts_with("unknown variant `fly`"));
+        }
+        _ => panic!("Expected syntax error"),
+    }
+
+    let source = r#"{
+  "bindings": {
+    "forward": ["KeyE"],
+    "backward": ["KeyE"]
+  }
+}"#;
+
+    match Controls::parse(source) {
+        Err(ControlsError::Syntax { line, message, .. }) => {
+            assert_eq!(line, 4);
+            assert_eq!(message, "button `KeyE` bound to both Forward and Backward");
+        }
+        _ => panic!("Expected syntax error"),
+    }
+}
+
+#[test]
+fn rebind() {
+    let controls =
+        Controls::parse(r#"{ "bindings": { "forward": ["KeyW", "MouseLeft"] } }"#).unwrap();
+    let mut keyboard = Keyboard::new(controls);
+
+    assert_eq!(keyboard.button("KeyE", true), None);
+    assert_eq!(
+        keyboard.button("MouseLeft", true),
+        Some((Action::Forward, 1.0))
+    );
+
+    // Releasing one of two held buttons keeps the action active
+    assert_eq!(keyboard.button("KeyW", true), Some((Action::Forward, 1.0)));
+    assert_eq!(
+        keyboard.button("MouseLeft", false),
+        Some((Action::Forward, 1.0))
+    );
+    assert_eq!(keyboard.button("KeyW", false), Some((Action::Forward, 0.0)));
+}
+
+#[test]
+fn strafe() {
+    let mut state = state();
+
+    state.sub.direction = PI / 2.0;
+    state.input(Action::StrafeRight, 1.0);
+
+    for _ in 0..10 {
+        update_state(&mut state, 0.0, DELTA, 0.0);
+    }
+
+    // Facing +y, strafing right moves towards -x without turning
+    assert!(state.sub.x < 25.0);
+    assert!((state.sub.y - 25.0).abs() < 1e-3);
+    assert_eq!(state.sub.direction, PI / 2.0);
+
+    // Diagonal movement is no faster than moving straight
+    let mut state = state_at(25.0, 25.0);
+    state.input(Action::Forward, 1.0);
+    state.input(Action::StrafeRight, 1.0);
+    update_state(&mut state, 0.0, DELTA, 0.0);
+
+    let distance = (state.sub.x - 25.0).hypot(state.sub.y - 25.0);
+    assert!((distance - state.sub.translate_speed * DELTA).abs() < 1e-3);
+}
+
+#[test]
+fn mouse_look() {
+    let mut state = state();
+    let keyboard = Keyboard::new(Controls::new());
+    let sensitivity = keyboard.controls.sensitivity;
+
+    for (x, y) in [(100.0, 50.0), (-20.0, 0.0)] {
+        for (action, value) in keyboard.motion(x, y) {
+            state.input(action, value);
+        }
+    }
+
+    update_state(&mut state, 0.0, DELTA, 0.0);
+
+    assert!((state.sub.direction - 80.0 * sensitivity).abs() < 1e-5);
+    assert_eq!(state.conf.look, 0.0);
+
+    // Moving the mouse down looks down
+    assert!((state.sub.pitch + 50.0 * sensitivity).abs() < 1e-5);
+    assert_eq!(state.conf.pitch, 0.0);
+
+    update_state(&mut state, 0.0, DELTA, 0.0);
+    assert!((state.sub.direction - 80.0 * sensitivity).abs() < 1e-5);
+}
@@ -0,0 +1,296 @@
+use std::f32::consts::PI;
+
+use proptest::prelude::*;
+
+use ray_casting_prototype::app::detection::{circle_collision, ray_collision, slide};
+use ray_casting_prototype::app::state::Orientation;
+
+const UNIT: f32 = 10.0;
+
+// Reference hit computed by testing the ray against every wall cell box
+struct Reference {
+    distance: f32,
+    cells: Vec<(i32, i32, Orientation)>,
+}
+
+// Compute entry distance and face of a ray into an axis aligned cell box
+fn enter_cell(x: f32, y: f32, angle: f32, cell_x: i32, cell_y: i32) -> Option<(f32, Orientation)> {
+    let (direction_x, direction_y) = (angle.cos() as f64, angle.sin() as f64);
+    let (x, y, unit) = (x as f64, y as f64, UNIT as f64);
+
+    let slab = |origin: f64, direction: f64, cell: i32| {
+        let min = cell as f64 * unit;
+        let max = min + unit;
+
+        if direction == 0.0 {
+            return if origin >= min && origin < max {
+                Some((f64::NEG_INFINITY, f64::INFINITY))
+            } else {
+                None
+            };
+        }
+
+        let (a, b) = ((min - origin) / direction, (max - origin) / direction);
+        Some((a.min(b), a.max(b)))
+    };
+
+    let (enter_x, exit_x) = slab(x, direction_x, cell_x)?;
+    let (enter_y, exit_y) = slab(y, direction_y, cell_y)?;
+
+    let enter = enter_x.max(enter_y);
+    let exit = exit_x.min(exit_y);
+
+    if enter > exit || enter < 0.0 {
+        return None;
+    }
+
+    let orientation = match (enter_x >= enter_y, direction_x > 0.0, direction_y > 0.0) {
+        (true, true, _) => Orientation::Right,
+        (true, false, _) => Orientation::Left,
+        (false, _, true) => Orientation::Up,
+        (false, _, false) => Orientation::Down,
+    };
+
+    Some((enter as f32, orientation))
+}
+
+// Find the nearest wall cells hit by a ray
+fn brute_force(x: f32, y: f32, angle: f32, grid: &[Vec<u32>]) -> Option<Reference> {
+    let mut hits = Vec::new();
+
+    for (cell_y, row) in grid.iter().enumerate() {
+        for (cell_x, id) in row.iter().enumerate() {
+            if *id == 0 {
+                continue;
+            }
+
+            if let Some((distance, orientation)) =
+                enter_cell(x, y, angle, cell_x as i32, cell_y as i32)
+            {
+                hits.push((distance, cell_x as i32, cell_y as i32, orientation));
+            }
+        }
+    }
+
+    let distance = hits
+        .iter()
+        .map(|(distance, ..)| *distance)
+        .fold(f32::INFINITY, f32::min);
+
+    if !distance.is_finite() {
+        return None;
+    }
+
+    let cells = hits
+        .into_iter()
+        .filter(|hit| (hit.0 - distance).abs() <= tolerance(distance))
+        .map(|(_, cell_x, cell_y, orientation)| (cell_x, cell_y, orientation))
+        .collect();
+
+    Some(Reference { distance, cells })
+}
+
+// Allowed distance error, relative to the ray length
+fn tolerance(distance: f32) -> f32 {
+    1e-3 * distance.max(UNIT)
+}
+
+// Check whether a hit point lies on a grid corner, where faces are ambiguous
+fn corner(x: f32, y: f32) -> bool {
+    let near = |value: f32| {
+        let fraction = (value / UNIT).rem_euclid(1.0);
+        fraction.min(1.0 - fraction) < 1e-3
+    };
+
+    near(x) && near(y)
+}
+
+// Random grid with the subject placed inside an empty cell
+fn scene() -> impl Strategy<Value = (Vec<Vec<u32>>, f32, f32)> {
+    (2usize..12, 2usize..12)
+        .prop_flat_map(|(width, height)| {
+            (
+                prop::collection::vec(
+                    prop::collection::vec(prop_oneof![3 => Just(0u32), 1 => 1u32..10], width),
+                    height,
+                ),
+                0..width,
+                0..height,
+                0.0f32..1.0,
+                0.0f32..1.0,
+            )
+        })
+        .prop_map(|(mut grid, cell_x, cell_y, fraction_x, fraction_y)| {
+            grid[cell_y][cell_x] = 0;
+
+            let x = (cell_x as f32 + fraction_x) * UNIT;
+            let y = (cell_y as f32 + fraction_y) * UNIT;
+
+            (grid, x, y)
+        })
+}
+
+// Angles biased towards the axis aligned directions where tangents degenerate
+fn angle() -> impl Strategy<Value = f32> {
+    prop_oneof![
+        0.0f32..PI * 2.0,
+        (0..4, -1e-4f32..1e-4).prop_map(|(quadrant, offset)| quadrant as f32 * PI / 2.0 + offset),
+        (0..4).prop_map(|quadrant| quadrant as f32 * PI / 2.0 + PI / 4.0),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn matches_brute_force((grid, x, y) in scene(), angle in angle()) {
+        let width = grid[0].len() as f32 * UNIT;
+        let height = grid.len() as f32 * UNIT;
+
+        let collision = ray_collision(x, y, width, height, angle, UNIT, &grid, &[], &[]);
+
+        match brute_force(x, y, angle, &grid) {
+            None => {
+                prop_assert_eq!(collision.id, -1);
+                prop_assert_eq!(collision.orientation, Orientation::None);
+                prop_assert!(collision.distance.is_infinite());
+            }
+            Some(reference) => {
+                prop_assert!(
+                    (collision.distance - reference.distance).abs() <= tolerance(reference.distance),
+                    "distance {} != {}",
+                    collision.distance,
+                    reference.distance
+                );
+
+                let cell = (collision.cell_x, collision.cell_y);
+                prop_assert_eq!(grid[cell.1 as usize][cell.0 as usize] as i32, collision.id);
+                prop_assert!(
+                    reference.cells.iter().any(|(cell_x, cell_y, _)| (*cell_x, *cell_y) == cell),
+                    "cell {:?} not in {:?}",
+                    cell,
+                    reference.cells.iter().map(|(x, y, _)| (*x, *y)).collect::<Vec<_>>()
+                );
+
+                if reference.cells.len() == 1 && !corner(collision.x, collision.y) {
+                    prop_assert_eq!(&collision.orientation, &reference.cells[0].2);
+                }
+
+                let expected_x = x + angle.cos() * collision.distance;
+                let expected_y = y + angle.sin() * collision.distance;
+                prop_assert!((collision.x - expected_x).abs() <= tolerance(collision.distance));
+                prop_assert!((collision.y - expected_y).abs() <= tolerance(collision.distance));
+                prop_assert!((0.0..=1.0).contains(&collision.offset));
+            }
+        }
+    }
+}
+
+#[test]
+fn thin_corner() {
+    // Two walls touching only at a corner must not let a diagonal ray through
+    let grid = vec![vec![0, 1], vec![1, 0]];
+    let collision = ray_collision(5.0, 5.0, 20.0, 20.0, PI / 4.0, UNIT, &grid, &[], &[]);
+
+    assert_eq!(collision.id, 1);
+    assert!((collision.distance - 50f32.sqrt()).abs() < 1e-4);
+}
+
+#[test]
+fn axis_aligned() {
+    let grid = vec![vec![1, 1, 1], vec![1, 0, 1], vec![1, 1, 1]];
+
+    for (index, orientation) in [
+        Orientation::Right,
+        Orientation::Up,
+        Orientation::Left,
+        Orientation::Down,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let angle = index as f32 * PI / 2.0;
+        let collision = ray_collision(15.0, 15.0, 30.0, 30.0, angle, UNIT, &grid, &[], &[]);
+
+        assert_eq!(collision.orientation, orientation);
+        assert!((collision.distance - 5.0).abs() < 1e-4);
+        assert!((collision.offset - 0.5).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn circle_overlap() {
+    let grid = vec![vec![1, 1, 1], vec![1, 0, 1], vec![1, 1, 1]];
+
+    assert!(!circle_collision(
+        15.0,
+        15.0,
+        2.0,
+        30.0,
+        30.0,
+        UNIT,
+        &grid,
+        &[],
+        &[]
+    ));
+    assert!(circle_collision(
+        18.5,
+        15.0,
+        2.0,
+        30.0,
+        30.0,
+        UNIT,
+        &grid,
+        &[],
+        &[]
+    ));
+    assert!(circle_collision(
+        18.0,
+        18.0,
+        2.5,
+        30.0,
+        30.0,
+        UNIT,
+        &grid,
+        &[],
+        &[]
+    ));
+
+    // Corner distance is √2 · 1.5 ≈ 2.12, so only the diagonal tile is near
+    let grid = vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 1]];
+    assert!(!circle_collision(
+        18.5,
+        18.5,
+        2.0,
+        30.0,
+        30.0,
+        UNIT,
+        &grid,
+        &[],
+        &[]
+    ));
+    assert!(circle_collision(
+        18.5,
+        18.5,
+        2.2,
+        30.0,
+        30.0,
+        UNIT,
+        &grid,
+        &[],
+        &[]
+    ));
+}
+
+#[test]
+fn slide_along_wall() {
+    let grid = vec![vec![1, 1, 1], vec![1, 0, 1], vec![1, 1, 1]];
+
+    // Moving diagonally into the east wall keeps the vertical component
+    let (x, y) = slide(17.5, 12.0, 1.0, 1.0, 2.0, 30.0, 30.0, UNIT, &grid, &[], &[]);
+    assert_eq!((x, y), (17.5, 13.0));
+
+    // Moving into a corner stops on both axes
+    let (x, y) = slide(17.5, 17.5, 1.0, 1.0, 2.0, 30.0, 30.0, UNIT, &grid, &[], &[]);
+    assert_eq!((x, y), (17.5, 17.5));
+}
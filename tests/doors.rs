@@ -0,0 +1,150 @@
+mod common;
+
+use common::{assert_golden, render_pose, Pose};
+
+use ray_casting_prototype::app::detection::ray_collision;
+use ray_casting_prototype::app::level::{Level, LevelError};
+use ray_casting_prototype::app::logic::update_state;
+use ray_casting_prototype::app::state::{Orientation, State};
+use ray_casting_prototype::display::window::Graphics;
+use ray_casting_prototype::input::controls::Action;
+
+const DELTA: f32 = 1.0 / 60.0;
+
+fn state() -> State {
+    State::load("maps/doors.map").unwrap()
+}
+
+// Cast a single ray through the loaded doors
+fn cast(state: &State, x: f32, y: f32, angle: f32) -> (i32, f32, Orientation) {
+    let map = state.env.map;
+    let collision = ray_collision(
+        x,
+        y,
+        map.width,
+        map.height,
+        angle,
+        state.env.unit,
+        &state.conf.grid,
+        &state.doors,
+        &state.segments,
+    );
+
+    (collision.id, collision.distance, collision.orientation)
+}
+
+#[test]
+fn parse_doors() {
+    let level = Level::load("maps/doors.map").unwrap();
+    assert_eq!(level.doors, vec![9]);
+
+    let state = state();
+    assert_eq!(state.doors.len(), 2);
+
+    // Doors span the corridor they block
+    assert_eq!((state.doors[0].x, state.doors[0].y), (11, 4));
+    assert!(!state.doors[0].vertical);
+    assert_eq!((state.doors[1].x, state.doors[1].y), (7, 5));
+    assert!(state.doors[1].vertical);
+
+    let source = "size 3 3\nspawn 1.5 1.5 0\ndoor 0\ngrid\n111\n1.1\n111\n";
+    assert!(matches!(
+        Level::parse(source),
+        Err(LevelError::Syntax { line: 3, .. })
+    ));
+}
+
+#[test]
+fn recessed_door() {
+    let mut state = state();
+
+    // Closed door is hit at the tile center
+    let (id, distance, orientation) = cast(&state, 35.0, 55.0, 0.0);
+    assert_eq!((id, orientation), (9, Orientation::Right));
+    assert!((distance - 40.0).abs() < 1e-4);
+
+    // Half open door still covers the far half of the tile
+    state.doors[1].open = 0.5;
+
+    let (id, distance, _) = cast(&state, 35.0, 57.0, 0.0);
+    assert_eq!(id, 9);
+    assert!((distance - 40.0).abs() < 1e-4);
+
+    // Rays through the opening reach the wall behind the door
+    let (id, distance, _) = cast(&state, 35.0, 52.0, 0.0);
+    assert_eq!(id, 1);
+    assert!((distance - 95.0).abs() < 1e-4);
+}
+
+#[test]
+fn blocks_while_closed() {
+    let mut state = state();
+
+    state.sub.x = 65.0;
+    state.input(Action::Forward, 1.0);
+
+    for _ in 0..60 {
+        update_state(&mut state, 0.0, DELTA, 0.0);
+    }
+
+    assert!(state.sub.x < 70.0 - state.sub.radius + 1e-3);
+
+    // Partly open doors keep blocking
+    state.doors[1].open = 0.9;
+    update_state(&mut state, 0.0, DELTA, 0.0);
+    assert!(state.sub.x < 70.0);
+
+    state.doors[1].open = 1.0;
+
+    for _ in 0..60 {
+        update_state(&mut state, 0.0, DELTA, 0.0);
+    }
+
+    assert!(state.sub.x > 80.0);
+}
+
+#[test]
+fn use_key_animates() {
+    let mut state = state();
+
+    state.sub.x = 60.0;
+
+    // Open over one second
+    state.input(Action::Use, 1.0);
+    update_state(&mut state, 0.0, 0.5, 0.0);
+    assert_eq!(state.doors[1].open, 0.5);
+
+    // Holding the key doesn't toggle again
+    update_state(&mut state, 0.0, 0.75, 0.0);
+    assert_eq!(state.doors[1].open, 1.0);
+    assert_eq!(state.doors[1].direction, 0.0);
+
+    // Press again to close
+    state.input(Action::Use, 0.0);
+    update_state(&mut state, 0.0, DELTA, 0.0);
+    state.input(Action::Use, 1.0);
+    update_state(&mut state, 0.0, 0.25, 0.0);
+    assert_eq!(state.doors[1].direction, -1.0);
+    assert!((state.doors[1].open - 0.75).abs() < 1e-5);
+
+    // Doors don't close on the subject standing in them
+    state.doors[1].open = 1.0;
+    state.sub.x = 75.0;
+    update_state(&mut state, 0.0, 0.25, 0.0);
+    assert_eq!(state.doors[1].open, 1.0);
+
+    // Doors out of reach are ignored
+    let mut state = self::state();
+    state.input(Action::Use, 1.0);
+    update_state(&mut state, 0.0, 0.5, 0.0);
+    assert_eq!(state.doors[1].open, 0.0);
+}
+
+#[test]
+fn half_open_door() {
+    let mut state = state();
+    state.doors[1].open = 0.5;
+
+    let actual = render_pose(&mut state, Pose::new(45.0, 50.0, 0.3));
+    assert_golden("half_open_door", &actual, 2);
+}
@@ -0,0 +1,101 @@
+use ray_casting_prototype::app::detection::cast_ray;
+use ray_casting_prototype::app::graphics::{cast_rays, draw_surface, draw_surface_rays};
+use ray_casting_prototype::app::logic::update_state;
+use ray_casting_prototype::app::state::{Frame, State};
+
+fn state() -> State {
+    let mut state = State::load("maps/segments.map").expect("Map should load");
+    update_state(&mut state, 0.0, 0.0, 0.0);
+    state
+}
+
+fn buffer(state: &State) -> Vec<u8> {
+    vec![0u8; (state.env.width * state.env.height * 4) as usize]
+}
+
+#[test]
+fn frame_is_cast_on_update() {
+    let state = state();
+    let frame = &state.frame;
+
+    assert_eq!(frame.rays.len(), state.conf.resolution as usize);
+    assert_eq!(frame.depth.len(), state.conf.resolution as usize);
+    assert_eq!(frame.rays, cast_rays(&state));
+
+    for (index, depth) in frame.depth.iter().enumerate() {
+        let nearest = frame
+            .nearest(index)
+            .expect("Every column should hit a wall");
+        let expected = nearest.distance * (state.sub.direction - nearest.angle).cos();
+
+        assert_eq!(*depth, expected);
+        assert!(frame.rays[index]
+            .windows(2)
+            .all(|pair| pair[0].distance <= pair[1].distance));
+    }
+}
+
+#[test]
+fn frame_follows_subject() {
+    let mut state = state();
+    let before = state.frame.clone();
+
+    state.sub.direction += 0.5;
+    update_state(&mut state, 0.0, 0.0, 0.0);
+
+    assert_ne!(before.rays, state.frame.rays);
+    assert_eq!(state.frame.rays, cast_rays(&state));
+}
+
+#[test]
+fn render_reads_frame() {
+    let mut state = state();
+    let (width, height) = (state.env.width, state.env.height);
+
+    let mut background = buffer(&state);
+    draw_surface(&mut state, &mut background, width, height);
+
+    let mut walls = background.clone();
+    draw_surface_rays(&mut state, &mut walls, width, height);
+
+    assert!(walls != background);
+
+    // Without cast rays the passes have nothing to draw
+    state.frame = Frame::new();
+
+    let mut empty = background.clone();
+    draw_surface_rays(&mut state, &mut empty, width, height);
+
+    assert!(empty == background);
+}
+
+// The depth of the centre column is the wall distance straight ahead after rotating
+#[test]
+fn depth_follows_rotation() {
+    let mut state = state();
+
+    state.conf.look = 0.4;
+    update_state(&mut state, 0.1, 0.1, 10.0);
+
+    let map = state.env.map;
+    let direction = state.sub.direction;
+
+    let ahead = cast_ray(
+        state.sub.x,
+        state.sub.y,
+        map.width,
+        map.height,
+        direction,
+        direction,
+        state.env.height as f32,
+        state.env.unit,
+        &state.conf.grid,
+        &state.doors,
+        &state.segments,
+        &state.conf.heights,
+    );
+
+    let centre = state.conf.resolution as usize / 2;
+
+    assert!((state.frame.depth[centre] - ahead[0].distance).abs() < 1e-3);
+}
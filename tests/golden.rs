@@ -0,0 +1,96 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use common::{assert_golden, render_pose, Pose};
+
+use ray_casting_prototype::app::data::{CROUCH, PITCH};
+use ray_casting_prototype::app::state::State;
+
+const TOLERANCE: u8 = 2;
+
+fn check(name: &str, pose: Pose) {
+    let mut state = State::build();
+    let image = render_pose(&mut state, pose);
+    assert_golden(name, &image, TOLERANCE);
+}
+
+#[test]
+fn spawn() {
+    check("spawn", Pose::new(120.0, 40.0, PI / 6.0));
+}
+
+#[test]
+fn corridor_east() {
+    check("corridor_east", Pose::new(25.0, 115.0, 0.0));
+}
+
+#[test]
+fn corridor_south() {
+    check("corridor_south", Pose::new(165.0, 25.0, PI / 2.0));
+}
+
+#[test]
+fn room_west() {
+    check("room_west", Pose::new(285.0, 165.0, PI));
+}
+
+#[test]
+fn corner_diagonal() {
+    check("corner_diagonal", Pose::new(295.0, 225.0, PI * 1.25));
+}
+
+#[test]
+fn pillars_north() {
+    check("pillars_north", Pose::new(65.0, 215.0, PI * 1.5 + 0.1));
+}
+
+#[test]
+fn textured_pillars() {
+    let mut state = State::load("maps/textured.map").unwrap();
+    let image = render_pose(&mut state, Pose::new(65.0, 215.0, PI * 1.5 + 0.1));
+    assert_golden("textured_pillars", &image, TOLERANCE);
+}
+
+#[test]
+fn textured_room() {
+    let mut state = State::load("maps/textured.map").unwrap();
+    let image = render_pose(&mut state, Pose::new(45.0, 35.0, PI * 0.3));
+    assert_golden("textured_room", &image, TOLERANCE);
+}
+
+#[test]
+fn textured_sprites() {
+    let mut state = State::load("maps/textured.map").unwrap();
+    let image = render_pose(&mut state, Pose::new(105.0, 45.0, -0.15));
+    assert_golden("textured_sprites", &image, TOLERANCE);
+}
+
+#[test]
+fn foggy_corridor() {
+    let mut state = State::load("maps/foggy.map").unwrap();
+    let image = render_pose(&mut state, Pose::new(165.0, 25.0, PI / 2.0));
+    assert_golden("foggy_corridor", &image, TOLERANCE);
+}
+
+#[test]
+fn crouched_looking_up() {
+    let mut state = State::load("maps/heights.map").unwrap();
+
+    state.sub.eye = CROUCH;
+    state.sub.pitch = PITCH;
+
+    let image = render_pose(&mut state, Pose::new(25.0, 60.0, 0.0));
+    assert_golden("crouched_looking_up", &image, TOLERANCE);
+}
+
+#[test]
+fn jumping_looking_down() {
+    let mut state = State::load("maps/textured.map").unwrap();
+
+    state.sub.elevation = 0.25;
+    state.sub.pitch = -PITCH;
+
+    let image = render_pose(&mut state, Pose::new(45.0, 35.0, PI * 0.3));
+    assert_golden("jumping_looking_down", &image, TOLERANCE);
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::PathBuf;
+
+use ray_casting_prototype::display::headless::{self, HeadlessState};
+use ray_casting_prototype::display::window::Graphics;
+use ray_casting_prototype::input::controls::Action;
+
+const WIDTH: u32 = 4;
+const HEIGHT: u32 = 3;
+
+// Fills each pixel with its column, row and update count, leaving alpha transparent
+#[derive(Default)]
+struct Stub {
+    updates: u32,
+    time: f32,
+}
+
+impl Graphics for Stub {
+    fn input(&mut self, _action: Action, _value: f32) {}
+    fn update(&mut self, time: f32, _delta: f32, _fps: f32) {
+        self.updates += 1;
+        self.time = time;
+    }
+    fn render(&mut self, buffer: &mut [u8], width: u32, _height: u32) {
+        for (index, pixel) in buffer.chunks_exact_mut(4).enumerate() {
+            let index = index as u32;
+            pixel.copy_from_slice(&[
+                (index % width) as u8,
+                (index / width) as u8,
+                self.updates as u8,
+                0,
+            ]);
+        }
+    }
+}
+
+#[test]
+fn frame_renders_buffer() {
+    let mut headless = HeadlessState::new(WIDTH, HEIGHT);
+    let mut stub = Stub::default();
+
+    headless.frame(&mut stub, 0.5);
+    let buffer = headless.frame(&mut stub, 0.5);
+
+    assert_eq!(buffer.len(), (WIDTH * HEIGHT * 4) as usize);
+    assert_eq!(&buffer[..4], &[0, 0, 2, 0]);
+    assert_eq!(&buffer[buffer.len() - 4..], &[3, 2, 2, 0]);
+    assert_eq!(stub.time, 1.0);
+}
+
+#[test]
+fn capture_renders_frames() {
+    let mut stub = Stub::default();
+
+    let buffer = headless::capture(WIDTH, HEIGHT, 3, 0.25, &mut stub);
+
+    assert_eq!(buffer.len(), (WIDTH * HEIGHT * 4) as usize);
+    assert_eq!(&buffer[(1 + 2 * WIDTH) as usize * 4..][..4], &[1, 2, 3, 0]);
+    assert_eq!(stub.updates, 3);
+}
+
+// Saved images are opaque, as presented by the window
+#[test]
+fn run_saves_opaque_image() {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("headless");
+    fs::create_dir_all(&directory).expect("Headless output directory should be created");
+    let path = directory.join("stub.png");
+
+    let mut stub = Stub::default();
+
+    headless::run(WIDTH, HEIGHT, 1, 1.0 / 60.0, &path, &mut stub).expect("Frame should be saved");
+
+    let image = image::open(&path)
+        .expect("Saved frame should load")
+        .to_rgba8();
+
+    assert_eq!(image.dimensions(), (WIDTH, HEIGHT));
+    assert_eq!(image.get_pixel(2, 1).0, [2, 1, 1, 255]);
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{assert_golden, render_pose, syntax, Pose};
+
+use ray_casting_prototype::app::detection::{cast_ray, ray_collision, ray_collisions};
+use ray_casting_prototype::app::level::Level;
+use ray_casting_prototype::app::state::State;
+
+const UNIT: f32 = 10.0;
+
+// Distances and ids of every hit along a ray heading east through a single row
+fn hits(row: Vec<u32>, heights: &[f32]) -> Vec<(i32, f32)> {
+    let width = row.len() as f32 * UNIT;
+    let grid = vec![row];
+
+    ray_collisions(5.0, 5.0, width, UNIT, 0.0, UNIT, &grid, &[], &[], heights)
+        .iter()
+        .map(|collision| (collision.id, collision.distance))
+        .collect()
+}
+
+#[test]
+fn casts_past_short_walls() {
+    let mut heights = [1.0; 10];
+    heights[2] = 0.25;
+    heights[3] = 0.5;
+
+    assert_eq!(
+        hits(vec![0, 2, 0, 3, 1, 1], &heights),
+        vec![(2, 5.0), (3, 25.0), (1, 35.0)]
+    );
+
+    // Without heights every wall hides everything behind it
+    assert_eq!(hits(vec![0, 2, 0, 3, 1, 1], &[]), vec![(2, 5.0)]);
+
+    // A tower is only hidden by walls as tall as itself
+    heights[4] = 2.0;
+    assert_eq!(
+        hits(vec![0, 1, 4, 1, 4, 0], &heights),
+        vec![(1, 5.0), (4, 15.0)]
+    );
+
+    // Leaving the map without a tall wall ends the list
+    assert_eq!(hits(vec![0, 2, 0], &heights), vec![(2, 5.0)]);
+}
+
+#[test]
+fn nearest_hit_unchanged() {
+    let grid = vec![vec![0, 2, 0, 1]];
+    let collision = ray_collision(5.0, 5.0, 40.0, 10.0, 0.0, UNIT, &grid, &[], &[]);
+
+    assert_eq!(collision.id, 2);
+    assert_eq!(collision.distance, 5.0);
+}
+
+#[test]
+fn projected_walls() {
+    let grid = vec![vec![0, 0, 2, 0, 1]];
+    let mut heights = [1.0; 10];
+    heights[2] = 0.5;
+
+    let rays = cast_ray(
+        5.0,
+        5.0,
+        50.0,
+        10.0,
+        0.0,
+        0.0,
+        100.0,
+        UNIT,
+        &grid,
+        &[],
+        &[],
+        &heights,
+    );
+
+    assert_eq!(rays.len(), 2);
+
+    // Projected unit heights shrink with distance, the exit is where the ray leaves the tile
+    assert_eq!(
+        (rays[0].wall, rays[0].height, rays[0].exit),
+        (0.5, projected(15.0), projected(25.0))
+    );
+    assert_eq!(
+        (rays[1].wall, rays[1].height, rays[1].exit),
+        (1.0, projected(35.0), projected(45.0))
+    );
+}
+
+// Projected height of a unit wall at distance with a surface height of 100
+fn projected(distance: f32) -> f32 {
+    100.0 / (distance / UNIT)
+}
+
+#[test]
+fn parse_heights() {
+    let level = Level::load("maps/heights.map").unwrap();
+    assert_eq!(
+        level.heights,
+        vec![(2, 0.25), (3, 0.5), (4, 0.75), (5, 2.5)]
+    );
+
+    let mut state = State::new();
+    state.apply(&level);
+    assert_eq!(state.conf.heights[1], 1.0);
+    assert_eq!(state.conf.heights[5], 2.5);
+
+    let source = "size 1 1\nspawn 0.5 0.5 0\nheight 1 0\ngrid\n.\n";
+    assert_eq!(
+        syntax(Level::parse(source)),
+        (3, 1, "height must be positive".to_string())
+    );
+
+    let source = "size 1 1\nspawn 0.5 0.5 0\nheight 12 1\ngrid\n.\n";
+    assert_eq!(syntax(Level::parse(source)).2, "unknown tile id 12");
+
+    let json = r#"{
+  "size": [1, 1],
+  "spawn": { "x": 0.5, "y": 0.5, "direction": 0 },
+  "heights": { "2": 0.5, "3": -1 },
+  "grid": ["."]
+}"#;
+
+    let (line, _, message) = syntax(Level::parse_json(json));
+    assert_eq!((line, message.as_str()), (4, "height must be positive"));
+
+    let level = Level::parse_json(&json.replace("-1", "3")).unwrap();
+    assert_eq!(level.heights, vec![(2, 0.5), (3, 3.0)]);
+}
+
+#[test]
+fn non_finite_heights() {
+    for value in ["NaN", "inf"] {
+        let source = format!("size 1 1\nspawn 0.5 0.5 0\nheight 1 {value}\ngrid\n.\n");
+        assert_eq!(
+            syntax(Level::parse(&source)),
+            (3, 10, format!("number `{value}` must be finite"))
+        );
+    }
+
+    let json = r#"{
+  "size": [1, 1],
+  "spawn": { "x": 0.5, "y": 0.5, "direction": 0 },
+  "heights": { "2": 1e39 },
+  "grid": ["."]
+}"#;
+
+    let (line, _, message) = syntax(Level::parse_json(json));
+    assert_eq!((line, message.as_str()), (4, "height must be finite"));
+}
+
+#[test]
+fn stacked_walls() {
+    let mut state = State::load("maps/heights.map").unwrap();
+    let image = render_pose(&mut state, Pose::new(25.0, 60.0, 0.0));
+    assert_golden("stacked_walls", &image, 2);
+}
@@ -0,0 +1,102 @@
+use ray_casting_prototype::app::level::Level;
+use ray_casting_prototype::app::logic::update_state;
+use ray_casting_prototype::app::state::State;
+use ray_casting_prototype::display::headless::HeadlessState;
+use ray_casting_prototype::display::window::Graphics;
+use ray_casting_prototype::input::backend::{Backend, Input};
+use ray_casting_prototype::input::controls::Action;
+use ray_casting_prototype::input::script::{Script, ScriptEvent};
+
+const DELTA: f32 = 1.0 / 60.0;
+
+const MAP: &str = "size 8 3
+unit 10
+spawn 1.5 1.5 0
+
+grid
+11111111
+1......1
+11111111
+";
+
+fn state() -> State {
+    let mut state = State::new();
+    state.apply(&Level::parse(MAP).unwrap());
+    state.init();
+    state.sub.translate_speed = 10.0;
+    state
+}
+
+#[test]
+fn script_events() {
+    let mut script = Script::new(vec![
+        ScriptEvent::new(1.0, Action::Forward, 0.0),
+        ScriptEvent::new(0.0, Action::Forward, 1.0),
+        ScriptEvent::new(0.5, Action::TurnLeft, 1.0),
+    ]);
+
+    assert_eq!(script.poll(0.0), vec![(Action::Forward, 1.0)]);
+    assert_eq!(script.poll(0.25), vec![]);
+    assert_eq!(
+        script.poll(2.0),
+        vec![(Action::TurnLeft, 1.0), (Action::Forward, 0.0)]
+    );
+    assert!(script.finished());
+
+    let mut input = Input::default();
+    input.attach(Script::new(vec![ScriptEvent::new(
+        0.0,
+        Action::Backward,
+        1.0,
+    )]));
+    input.attach(Script::new(vec![ScriptEvent::new(0.0, Action::Look, 0.5)]));
+
+    assert_eq!(
+        input.poll(0.0),
+        vec![(Action::Backward, 1.0), (Action::Look, 0.5)]
+    );
+}
+
+#[test]
+fn scripted_walk() {
+    let mut state = state();
+    let mut headless = HeadlessState::new(96, 72);
+
+    // Walk forward for one second, starting on the first frame
+    headless.attach(Script::new(vec![
+        ScriptEvent::new(0.0, Action::Forward, 1.0),
+        ScriptEvent::new(1.0, Action::Forward, 0.0),
+    ]));
+
+    for _ in 0..90 {
+        headless.frame(&mut state, DELTA);
+    }
+
+    assert!((state.sub.x - 25.0).abs() < 0.2);
+    assert_eq!(state.sub.y, 15.0);
+}
+
+#[test]
+fn analog_values() {
+    let mut state = state();
+
+    // Half deflection moves at half speed
+    state.input(Action::Forward, 0.5);
+
+    for _ in 0..60 {
+        update_state(&mut state, 0.0, DELTA, 0.0);
+    }
+
+    assert!((state.sub.x - 20.0).abs() < 1e-3);
+
+    // Opposing actions cancel out
+    state.input(Action::Backward, 0.5);
+    update_state(&mut state, 0.0, DELTA, 0.0);
+    assert!((state.sub.x - 20.0).abs() < 1e-3);
+
+    // Values are clamped to the unit range
+    state.input(Action::Forward, 0.0);
+    state.input(Action::Backward, 4.0);
+    update_state(&mut state, 0.0, 0.5, 0.0);
+    assert!((state.sub.x - 15.0).abs() < 1e-3);
+}
@@ -0,0 +1,315 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use common::{assert_golden, render_pose, syntax, Pose};
+
+use ray_casting_prototype::app::data::grid;
+use ray_casting_prototype::app::level::{Level, LevelError};
+use ray_casting_prototype::app::state::{FogMode, State};
+
+const MAP: &str = "size 4 3
+unit 8
+spawn 1.5 1.5 90
+
+grid
+1111
+1..1
+1111
+";
+
+#[test]
+fn parse_ascii() {
+    let level = Level::parse(MAP).unwrap();
+
+    assert_eq!((level.width, level.height), (4, 3));
+    assert_eq!(level.unit, 8.0);
+    assert_eq!(level.grid[1], vec![1, 0, 0, 1]);
+    assert_eq!(level.spawn.x, 1.5);
+    assert!((level.spawn.direction - PI / 2.0).abs() < 1e-6);
+}
+
+#[test]
+fn load_default_files() {
+    for path in ["maps/default.map", "maps/default.json"] {
+        let level = Level::load(path).unwrap();
+
+        assert_eq!(level.grid, grid(), "{path}");
+        assert_eq!((level.spawn.x, level.spawn.y), (12.0, 4.0), "{path}");
+        assert_eq!(level.palette.len(), 9, "{path}");
+    }
+}
+
+#[test]
+fn render_default_file() {
+    let mut state = State::load("maps/default.map").unwrap();
+    let image = render_pose(&mut state, Pose::new(120.0, 40.0, PI / 6.0));
+    assert_golden("spawn", &image, 2);
+}
+
+#[test]
+fn ragged_row() {
+    let source = MAP.replace("1..1", "1..");
+    assert_eq!(syntax(Level::parse(&source)).0, 7);
+    assert_eq!(syntax(Level::parse(&source)).1, 4);
+
+    let source = MAP.replace("1..1", "1..11");
+    let (line, column, message) = syntax(Level::parse(&source));
+    assert_eq!((line, column), (7, 5));
+    assert_eq!(message, "expected 4 tiles, found 5");
+}
+
+#[test]
+fn invalid_tile() {
+    let source = MAP.replace("1..1", "1.x1");
+    let (line, column, message) = syntax(Level::parse(&source));
+    assert_eq!((line, column), (7, 3));
+    assert_eq!(message, "invalid tile `x`");
+}
+
+#[test]
+fn missing_rows() {
+    let source = MAP.replace("1111\n", "");
+    let (line, _, message) = syntax(Level::parse(&source));
+    assert_eq!(line, 7);
+    assert_eq!(message, "expected 3 grid rows, found 1");
+}
+
+#[test]
+fn invalid_directive() {
+    let source = MAP.replace("unit 8", "unit eight");
+    assert_eq!(syntax(Level::parse(&source)).0, 2);
+    assert_eq!(syntax(Level::parse(&source)).1, 6);
+
+    let source = MAP.replace("unit 8", "scale 8");
+    assert_eq!(syntax(Level::parse(&source)).0, 2);
+
+    let source = MAP.replace("spawn 1.5 1.5 90", "spawn 0.5 0.5 90");
+    assert_eq!(syntax(Level::parse(&source)).0, 3);
+}
+
+#[test]
+fn non_finite_unit() {
+    for value in ["NaN", "inf", "-inf", "1e39"] {
+        let source = MAP.replace("unit 8", &format!("unit {value}"));
+        let (line, column, message) = syntax(Level::parse(&source));
+        assert_eq!((line, column), (2, 6), "{value}");
+        assert_eq!(message, format!("number `{value}` must be finite"));
+    }
+
+    let source = r#"{
+  "size": [1, 1],
+  "unit": 1e39,
+  "spawn": { "x": 0.5, "y": 0.5, "direction": 0 },
+  "grid": ["."]
+}"#;
+    let (line, column, message) = syntax(Level::parse_json(source));
+    assert_eq!((line, column), (3, 3));
+    assert_eq!(message, "unit must be finite and positive");
+}
+
+#[test]
+fn json_errors() {
+    let source = r#"{
+  "size": [2, 2],
+  "spawn": { "x": 0.5, "y": 0.5, "direction": 0 },
+  "grid": [
+    "..",
+    ".y"
+  ]
+}"#;
+
+    let (line, column, message) = syntax(Level::parse_json(source));
+    assert_eq!((line, column), (6, 7));
+    assert_eq!(message, "invalid tile `y`");
+
+    let source = source.replace("\"..\",", "\"..\"");
+    let (line, _, _) = syntax(Level::parse_json(&source));
+    assert_eq!(line, 6);
+}
+
+#[test]
+fn load_textures() {
+    let level = Level::load("maps/textured.map").unwrap();
+    let (id, path) = &level.textures[0];
+
+    assert_eq!(*id, 1);
+    assert!(path.ends_with("maps/textures/stone.png"));
+
+    let state = State::load("maps/textured.map").unwrap();
+    assert_eq!(state.textures.walls.len(), level.textures.len());
+
+    let path = std::env::temp_dir().join("ray-casting-missing-texture.map");
+    std::fs::write(&path, MAP.replace("grid", "texture 1 missing.png\n\ngrid")).unwrap();
+
+    assert!(matches!(State::load(&path), Err(LevelError::Texture(..))));
+}
+
+#[test]
+fn floor_and_ceiling() {
+    let source = format!("{MAP}\nfloor\n2222\n2..2\n2222\n\nceiling\n....\n.33.\n....\n");
+    let level = Level::parse(&source).unwrap();
+
+    assert_eq!(level.floor[0], vec![2, 2, 2, 2]);
+    assert_eq!(level.ceiling[1], vec![0, 3, 3, 0]);
+
+    let source = format!("{MAP}\nfloor\n2222\n2..2\n");
+    let (line, _, message) = syntax(Level::parse(&source));
+    assert_eq!(line, 13);
+    assert_eq!(message, "expected 3 floor rows, found 2");
+}
+
+#[test]
+fn sprites() {
+    let source = MAP.replace(
+        "grid",
+        "sprite 2.5 1.5 lamp.png\nsprite 1.5 1.5 barrel.png 0.5\n\ngrid",
+    );
+    let level = Level::parse(&source).unwrap();
+
+    assert_eq!(level.sprites.len(), 2);
+    assert_eq!(level.sprites[1].scale, 0.5);
+    assert_eq!(level.sprites[0].texture.to_str(), Some("lamp.png"));
+
+    let source = MAP.replace("grid", "sprite 5 1 lamp.png\n\ngrid");
+    assert_eq!(syntax(Level::parse(&source)).0, 5);
+
+    let state = State::load("maps/textured.map").unwrap();
+    assert_eq!(state.sprites.len(), 7);
+    assert_eq!(state.textures.sprites.len(), 3);
+}
+
+#[test]
+fn fog() {
+    let source = MAP.replace(
+        "grid",
+        "fog linear #102030 10 100\nattenuation 0.01\n\ngrid",
+    );
+    let level = Level::parse(&source).unwrap();
+
+    assert_eq!(level.fog.mode, FogMode::Linear);
+    assert_eq!((level.fog.start, level.fog.end), (10.0, 100.0));
+    assert_eq!(level.fog.attenuation, 0.01);
+    assert_eq!(level.fog.amount(55.0), 0.5);
+    assert_eq!(level.fog.amount(200.0), 1.0);
+
+    let source = MAP.replace("grid", "fog exponential #000000 0.05\n\ngrid");
+    let level = Level::parse(&source).unwrap();
+    assert_eq!(level.fog.mode, FogMode::Exponential);
+    assert_eq!(level.fog.density, 0.05);
+
+    let source = MAP.replace("grid", "fog cubic #000000 1\n\ngrid");
+    let (line, column, message) = syntax(Level::parse(&source));
+    assert_eq!((line, column), (5, 5));
+    assert_eq!(message, "unknown fog mode `cubic`");
+
+    let source = MAP.replace("grid", "fog linear #000000 100 10\n\ngrid");
+    let (line, column, message) = syntax(Level::parse(&source));
+    assert_eq!((line, column), (5, 24));
+    assert_eq!(message, "fog end must be greater than start");
+
+    let source = MAP.replace("grid", "fog linear #000000 10 10\n\ngrid");
+    assert_eq!(syntax(Level::parse(&source)).1, 23);
+
+    let source = MAP.replace("grid", "fog exponential #000000 -0.05\n\ngrid");
+    let (line, column, message) = syntax(Level::parse(&source));
+    assert_eq!((line, column), (5, 25));
+    assert_eq!(message, "fog distances and density must not be negative");
+
+    let source = MAP.replace("grid", "attenuation -0.01\n\ngrid");
+    let (line, column, message) = syntax(Level::parse(&source));
+    assert_eq!((line, column), (5, 13));
+    assert_eq!(message, "attenuation must not be negative");
+
+    let source = MAP.replace("grid", "fog linear #000000 10 inf\n\ngrid");
+    let (line, column, message) = syntax(Level::parse(&source));
+    assert_eq!((line, column), (5, 23));
+    assert_eq!(message, "number `inf` must be finite");
+
+    let source = MAP.replace("grid", "fog exponential #000000 NaN\n\ngrid");
+    assert_eq!(syntax(Level::parse(&source)).1, 25);
+
+    let source = MAP.replace("grid", "attenuation NaN\n\ngrid");
+    assert_eq!(syntax(Level::parse(&source)).1, 13);
+}
+
+#[test]
+fn json_fog_errors() {
+    let level = |fog: &str| {
+        format!(
+            r#"{{
+  "size": [1, 1],
+  "spawn": {{ "x": 0.5, "y": 0.5, "direction": 0 }},
+  "grid": ["."],
+  {fog}
+}}"#
+        )
+    };
+
+    let source =
+        level(r##""fog": { "mode": "linear", "color": "#000000", "start": 50, "end": 20 }"##);
+    let (line, column, message) = syntax(Level::parse_json(&source));
+    assert_eq!((line, column), (5, 63));
+    assert_eq!(message, "fog end must be greater than start");
+
+    let source = level(r##""fog": { "mode": "exponential", "color": "#000000", "density": -1 }"##);
+    let (line, column, message) = syntax(Level::parse_json(&source));
+    assert_eq!((line, column), (5, 55));
+    assert_eq!(message, "fog distances and density must not be negative");
+
+    let source = level(r#""attenuation": -0.5"#);
+    let (line, column, message) = syntax(Level::parse_json(&source));
+    assert_eq!((line, column), (5, 3));
+    assert_eq!(message, "attenuation must not be negative");
+
+    let source =
+        level(r##""fog": { "mode": "linear", "color": "#000000", "start": 0, "end": 1e39 }"##);
+    let (line, column, message) = syntax(Level::parse_json(&source));
+    assert_eq!((line, column), (5, 62));
+    assert_eq!(message, "fog end must be finite");
+
+    let source = level(r#""attenuation": 1e39"#);
+    let (line, column, message) = syntax(Level::parse_json(&source));
+    assert_eq!((line, column), (5, 3));
+    assert_eq!(message, "attenuation must be finite");
+}
+
+// Maps larger than the minimap fraction are scaled down into the bottom right corner
+#[test]
+fn large_map_minimap() {
+    let (columns, rows) = (120, 90);
+
+    let mut source = format!("size {columns} {rows}\nunit 10\nspawn 1.5 1.5 0\n\ngrid\n");
+    for row in 0..rows {
+        let tile = |column| row == 0 || row == rows - 1 || column == 0 || column == columns - 1;
+        let line: String = (0..columns)
+            .map(|column| if tile(column) { '1' } else { '.' })
+            .collect();
+        source.push_str(&line);
+        source.push('\n');
+    }
+
+    let mut state = State::new();
+    state.apply(&Level::parse(&source).unwrap());
+    state.init();
+
+    let env = &state.env;
+    let (width, height) = (env.width as f32, env.height as f32);
+    let (left, top) = (env.map.x, env.map.y);
+
+    assert_eq!((env.map.width, env.map.height), (1200.0, 900.0));
+    assert!(env.scale < 1.0);
+    assert!(env.map.x >= width * 2.0 / 3.0 - 0.01 && env.map.y >= height * 2.0 / 3.0 - 0.01);
+
+    let corner = env.minimap(env.map.width, env.map.height);
+    assert!((corner.x - width).abs() < 0.01 && (corner.y - height).abs() < 0.01);
+
+    // The 3D view outside the minimap is left uncovered
+    let image = render_pose(&mut state, Pose::new(15.0, 15.0, 0.0));
+    let map = state.colors.map;
+    let covered = image
+        .enumerate_pixels()
+        .filter(|(x, y, _)| (*x as f32) < left || (*y as f32) < top)
+        .any(|(_, _, pixel)| pixel.0 == [map.red, map.green, map.blue, map.alpha]);
+    assert!(!covered);
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use common::{black, lit, red, WHITE};
+
+use ray_casting_prototype::canvas;
+use ray_casting_prototype::graphics::color::Blend;
+use ray_casting_prototype::graphics::graphic::Graphic;
+use ray_casting_prototype::graphics::line::{Line, Stroke};
+
+const WIDTH: u32 = 16;
+const HEIGHT: u32 = 12;
+
+#[test]
+fn clip_inside() {
+    let line = Line::new(1.0, 2.0, 5.0, 7.0);
+    assert_eq!(line.clip(0.0, 0.0, 10.0, 10.0), Some(line));
+}
+
+#[test]
+fn clip_crossing() {
+    let line = Line::new(-5.0, 5.0, 15.0, 5.0);
+    assert_eq!(
+        line.clip(0.0, 0.0, 10.0, 10.0),
+        Some(Line::new(0.0, 5.0, 10.0, 5.0))
+    );
+
+    // Diagonal through two corners keeps its slope
+    let line = Line::new(-10.0, -5.0, 30.0, 15.0);
+    let clipped = line.clip(0.0, 0.0, 10.0, 10.0).unwrap();
+
+    assert_eq!(clipped, Line::new(0.0, 0.0, 10.0, 5.0));
+}
+
+#[test]
+fn clip_outside() {
+    assert_eq!(
+        Line::new(-5.0, 1.0, -1.0, 9.0).clip(0.0, 0.0, 10.0, 10.0),
+        None
+    );
+    assert_eq!(
+        Line::new(-5.0, 12.0, 15.0, 12.0).clip(0.0, 0.0, 10.0, 10.0),
+        None
+    );
+
+    // Passes by the corner
+    assert_eq!(
+        Line::new(8.0, -4.0, 14.0, 2.0).clip(0.0, 0.0, 10.0, 10.0),
+        None
+    );
+}
+
+#[test]
+fn aliased_line_keeps_slope_off_screen() {
+    // Leaves the buffer at the bottom, clamping would bend it along the last row
+    let line = Line::new(0.0, 0.0, 30.0, 30.0);
+
+    let mut buffer = black(WIDTH, HEIGHT);
+    canvas::line::render(&mut buffer, WIDTH, HEIGHT, &line, &WHITE, Blend::Normal);
+
+    let pixels = lit(&buffer, WIDTH, HEIGHT);
+
+    assert!(!pixels.is_empty());
+    assert!(pixels.iter().all(|(x, y)| x == y), "{pixels:?}");
+}
+
+#[test]
+fn smooth_line_coverage() {
+    // On pixel centers a horizontal line covers a single row
+    let mut buffer = black(WIDTH, HEIGHT);
+    let line = Line::new(2.0, 3.0, 10.0, 3.0);
+    canvas::line::render_smooth(&mut buffer, WIDTH, HEIGHT, &line, &WHITE, Blend::Normal);
+
+    for x in 3..10 {
+        assert_eq!(red(&buffer, WIDTH, x, 3), 255);
+        assert_eq!(red(&buffer, WIDTH, x, 2), 0);
+        assert_eq!(red(&buffer, WIDTH, x, 4), 0);
+    }
+
+    // Half way between rows it is split over both
+    let mut buffer = black(WIDTH, HEIGHT);
+    let line = Line::new(2.0, 5.5, 10.0, 5.5);
+    canvas::line::render_smooth(&mut buffer, WIDTH, HEIGHT, &line, &WHITE, Blend::Normal);
+
+    for x in 3..10 {
+        assert_eq!(red(&buffer, WIDTH, x, 5), 128);
+        assert_eq!(red(&buffer, WIDTH, x, 6), 128);
+    }
+}
+
+#[test]
+fn smooth_line_steep() {
+    let mut buffer = black(WIDTH, HEIGHT);
+    let line = Line::new(7.25, 1.0, 7.25, 9.0);
+    canvas::line::render_smooth(&mut buffer, WIDTH, HEIGHT, &line, &WHITE, Blend::Normal);
+
+    for y in 2..9 {
+        assert_eq!(red(&buffer, WIDTH, 7, y), 191);
+        assert_eq!(red(&buffer, WIDTH, 8, y), 64);
+    }
+}
+
+#[test]
+fn smooth_line_matches_unclipped() {
+    // Crossing the buffer, drawn the same as inside a larger buffer
+    let line = Line::new(-20.0, -7.0, 40.0, 20.0);
+
+    let mut clipped = black(WIDTH, HEIGHT);
+    canvas::line::render_smooth(&mut clipped, WIDTH, HEIGHT, &line, &WHITE, Blend::Normal);
+
+    let (large_width, large_height) = (WIDTH * 8, HEIGHT * 8);
+    let (offset_x, offset_y) = (WIDTH * 4, HEIGHT * 4);
+
+    let mut large = vec![0u8; (large_width * large_height * 4) as usize];
+    let shifted = Line::new(
+        line.x0 + offset_x as f32,
+        line.y0 + offset_y as f32,
+        line.x1 + offset_x as f32,
+        line.y1 + offset_y as f32,
+    );
+    canvas::line::render_smooth(
+        &mut large,
+        large_width,
+        large_height,
+        &shifted,
+        &WHITE,
+        Blend::Replace,
+    );
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let index = (((x + offset_x) + (y + offset_y) * large_width) * 4) as usize;
+            let alpha = large[index + 3] as i32;
+
+            assert!(
+                (red(&clipped, WIDTH, x, y) as i32 - alpha).abs() <= 1,
+                "pixel {x} {y}"
+            );
+        }
+    }
+}
+
+#[test]
+fn graphic_stroke() {
+    let line = Line::new(1.0, 1.5, 12.0, 1.5);
+
+    let aliased = Graphic::line(line, WHITE);
+    let mut smooth = aliased.clone();
+    smooth.set_stroke(Stroke::Smooth);
+
+    let mut buffer = black(WIDTH, HEIGHT);
+    canvas::graphics::render(&mut buffer, WIDTH, HEIGHT, &vec![&aliased]);
+    assert_eq!(red(&buffer, WIDTH, 5, 2), 255);
+
+    let mut buffer = black(WIDTH, HEIGHT);
+    canvas::graphics::render(&mut buffer, WIDTH, HEIGHT, &vec![&smooth]);
+    assert_eq!(red(&buffer, WIDTH, 5, 1), 128);
+    assert_eq!(red(&buffer, WIDTH, 5, 2), 128);
+}
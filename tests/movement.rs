@@ -0,0 +1,207 @@
+use ray_casting_prototype::app::data::{CROUCH, EYE, PITCH};
+use ray_casting_prototype::app::detection::circle_collision;
+use ray_casting_prototype::app::level::Level;
+use ray_casting_prototype::app::logic::update_state;
+use ray_casting_prototype::app::state::State;
+use ray_casting_prototype::display::window::Graphics;
+use ray_casting_prototype::input::controls::Action;
+
+const DELTA: f32 = 1.0 / 60.0;
+
+const MAP: &str = "size 5 5
+unit 10
+spawn 2.5 2.5 0
+
+grid
+11111
+1...1
+1...1
+1...1
+11111
+";
+
+fn state() -> State {
+    let mut state = State::new();
+    state.apply(&Level::parse(MAP).unwrap());
+    state.init();
+    state
+}
+
+#[test]
+fn slides_along_walls() {
+    let mut state = state();
+
+    state.sub.direction = 0.3;
+    state.input(Action::Forward, 1.0);
+
+    for _ in 0..80 {
+        update_state(&mut state, 0.0, DELTA, 0.0);
+
+        let (x, y, radius) = (state.sub.x, state.sub.y, state.sub.radius);
+        let map = state.env.map;
+
+        assert!(!circle_collision(
+            x,
+            y,
+            radius,
+            map.width,
+            map.height,
+            10.0,
+            &state.conf.grid,
+            &state.doors,
+            &state.segments
+        ));
+    }
+
+    // The subject ends up in the south-east corner instead of stopping at the first wall contact
+    assert!(state.sub.x > 40.0 - state.sub.radius - 1.0);
+    assert!(state.sub.y > 40.0 - state.sub.radius - 1.0);
+}
+
+#[test]
+fn keeps_radius_from_walls() {
+    let mut state = state();
+
+    state.sub.direction = 0.0;
+    state.input(Action::Forward, 1.0);
+
+    for _ in 0..40 {
+        update_state(&mut state, 0.0, DELTA, 0.0);
+    }
+
+    let gap = 40.0 - state.sub.x;
+    assert!(gap >= state.sub.radius && gap < state.sub.radius + state.sub.translate_speed * DELTA);
+    assert_eq!(state.sub.y, 25.0);
+}
+
+// Walk east from the spawn for a duration at a given frame rate
+fn walk(state: &mut State, seconds: f32, fps: f32) {
+    let frames = (seconds * fps).round() as u32;
+
+    state.input(Action::Forward, 1.0);
+
+    for frame in 0..frames {
+        update_state(state, frame as f32 / fps, 1.0 / fps, fps);
+    }
+
+    state.input(Action::Forward, 0.0);
+}
+
+#[test]
+fn frame_rate_independent() {
+    let mut slow = state();
+    let mut fast = state();
+
+    slow.sub.translate_speed = 10.0;
+    fast.sub.translate_speed = 10.0;
+
+    walk(&mut slow, 0.5, 20.0);
+    walk(&mut fast, 0.5, 240.0);
+
+    assert!((slow.sub.x - 30.0).abs() < 1e-3);
+    assert!((fast.sub.x - 30.0).abs() < 1e-3);
+
+    let mut state = state();
+    state.input(Action::TurnRight, 1.0);
+
+    for _ in 0..30 {
+        update_state(&mut state, 0.0, 1.0 / 30.0, 30.0);
+    }
+
+    assert!((state.sub.direction - state.sub.rotate_speed).abs() < 1e-3);
+}
+
+#[test]
+fn acceleration_and_friction() {
+    let mut state = state();
+
+    state.sub.translate_speed = 10.0;
+    state.sub.acceleration = 20.0;
+    state.sub.friction = 40.0;
+
+    walk(&mut state, 0.25, 60.0);
+    assert!((state.sub.velocity - 5.0).abs() < 1e-3);
+    assert!((state.sub.x - (25.0 + 0.5 * 20.0 * 0.25 * 0.25)).abs() < 0.1);
+
+    walk(&mut state, 0.5, 60.0);
+    assert_eq!(state.sub.velocity, 10.0);
+
+    let x = state.sub.x;
+
+    for _ in 0..20 {
+        update_state(&mut state, 0.0, 1.0 / 60.0, 60.0);
+    }
+
+    assert_eq!(state.sub.velocity, 0.0);
+    assert!(state.sub.x > x && state.sub.x < x + 10.0 * 0.25);
+}
+
+#[test]
+fn look_up_and_down() {
+    let mut state = state();
+
+    state.input(Action::LookUp, 1.0);
+    update_state(&mut state, 0.0, 0.1, 10.0);
+    assert!((state.sub.pitch - state.sub.pitch_speed * 0.1).abs() < 1e-5);
+
+    // Pitch stops at the limit
+    for _ in 0..20 {
+        update_state(&mut state, 0.0, 0.1, 10.0);
+    }
+
+    assert_eq!(state.sub.pitch, PITCH);
+
+    state.input(Action::LookUp, 0.0);
+    state.input(Action::Pitch, -10.0);
+    update_state(&mut state, 0.0, 0.1, 10.0);
+    assert_eq!(state.sub.pitch, -PITCH);
+}
+
+#[test]
+fn jump_and_land() {
+    let mut state = state();
+    let (jump_speed, gravity) = (state.sub.jump_speed, state.sub.gravity);
+
+    state.input(Action::Jump, 1.0);
+    update_state(&mut state, 0.0, DELTA, 60.0);
+    state.input(Action::Jump, 0.0);
+
+    let mut peak: f32 = 0.0;
+    let mut frames = 1;
+
+    while state.sub.elevation > 0.0 {
+        peak = peak.max(state.sub.elevation);
+        update_state(&mut state, 0.0, DELTA, 60.0);
+        frames += 1;
+    }
+
+    // Airtime and peak follow the ballistic arc within a frame
+    let airtime = 2.0 * jump_speed / gravity;
+    assert!((frames as f32 * DELTA - airtime).abs() <= DELTA);
+    assert!((peak - jump_speed * jump_speed / (2.0 * gravity)).abs() < 0.02);
+    assert_eq!(state.sub.vertical_velocity, 0.0);
+    assert_eq!(state.sub.eye_height(), EYE);
+}
+
+#[test]
+fn crouch() {
+    let mut state = state();
+
+    state.input(Action::Crouch, 1.0);
+    update_state(&mut state, 0.0, 0.05, 20.0);
+    assert!(state.sub.eye < EYE && state.sub.eye > CROUCH);
+
+    for _ in 0..10 {
+        update_state(&mut state, 0.0, 0.05, 20.0);
+    }
+
+    assert_eq!(state.sub.eye, CROUCH);
+
+    state.input(Action::Crouch, 0.0);
+
+    for _ in 0..10 {
+        update_state(&mut state, 0.0, 0.05, 20.0);
+    }
+
+    assert_eq!(state.sub.eye, EYE);
+}
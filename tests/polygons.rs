@@ -0,0 +1,153 @@
+mod common;
+
+use common::{black, red, RED};
+
+use ray_casting_prototype::canvas;
+use ray_casting_prototype::graphics::color::{Blend, Color};
+use ray_casting_prototype::graphics::graphic::Graphic;
+use ray_casting_prototype::graphics::point::Point;
+use ray_casting_prototype::graphics::polygon::{Fill, Polygon};
+
+const WIDTH: u32 = 20;
+const HEIGHT: u32 = 20;
+
+fn points(list: &[(f32, f32)]) -> Vec<Point> {
+    list.iter().map(|(x, y)| Point::new(*x, *y)).collect()
+}
+
+// Star drawn in one stroke, its center wound twice
+fn star(fill: Fill) -> Polygon {
+    let list = [
+        (10.0, 1.0),
+        (15.5, 18.0),
+        (1.0, 7.5),
+        (19.0, 7.5),
+        (4.5, 18.0),
+    ];
+
+    Polygon::new(points(&list), fill, true)
+}
+
+// Pixels are filled exactly where their centers are inside
+fn assert_filled(buffer: &[u8], polygon: &Polygon) {
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let inside = polygon.contains(x as f32 + 0.5, y as f32 + 0.5);
+            assert_eq!(red(buffer, WIDTH, x, y) > 0, inside, "pixel {x} {y}");
+        }
+    }
+}
+
+#[test]
+fn contains() {
+    let square = Polygon::new(
+        points(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]),
+        Fill::EvenOdd,
+        true,
+    );
+
+    assert!(square.contains(5.0, 5.0));
+    assert!(!square.contains(15.0, 5.0));
+    assert!(!square.contains(5.0, -1.0));
+
+    assert!(!star(Fill::EvenOdd).contains(10.0, 10.0));
+    assert!(star(Fill::NonZero).contains(10.0, 10.0));
+    assert!(star(Fill::EvenOdd).contains(10.0, 4.0));
+}
+
+#[test]
+fn winding() {
+    let clockwise = Polygon::triangle(
+        Point::new(0.0, 0.0),
+        Point::new(10.0, 0.0),
+        Point::new(0.0, 10.0),
+        true,
+    );
+
+    assert_eq!(clockwise.winding(2.0, 2.0).abs(), 1);
+    assert_eq!(clockwise.winding(8.0, 8.0), 0);
+    assert_eq!(star(Fill::NonZero).winding(10.0, 10.0).abs(), 2);
+}
+
+#[test]
+fn fill_triangle() {
+    let triangle = Polygon::triangle(
+        Point::new(2.0, 1.0),
+        Point::new(17.5, 9.0),
+        Point::new(6.0, 18.5),
+        true,
+    );
+
+    let mut buffer = black(WIDTH, HEIGHT);
+    canvas::polygon::render(&mut buffer, WIDTH, HEIGHT, &triangle, &RED, Blend::Normal);
+
+    assert_filled(&buffer, &triangle);
+}
+
+#[test]
+fn fill_rules() {
+    for fill in [Fill::EvenOdd, Fill::NonZero] {
+        let polygon = star(fill);
+
+        let mut buffer = black(WIDTH, HEIGHT);
+        canvas::polygon::render(&mut buffer, WIDTH, HEIGHT, &polygon, &RED, Blend::Normal);
+
+        assert_filled(&buffer, &polygon);
+    }
+}
+
+#[test]
+fn fill_clipped_once() {
+    // Overhangs every side, translucent to catch pixels blended twice
+    let polygon = Polygon::new(
+        points(&[(-5.0, 10.0), (10.0, -5.0), (25.0, 10.0), (10.0, 25.0)]),
+        Fill::NonZero,
+        true,
+    );
+    let color = Color::new(255, 0, 0, 128);
+
+    let mut buffer = black(WIDTH, HEIGHT);
+    canvas::polygon::render(&mut buffer, WIDTH, HEIGHT, &polygon, &color, Blend::Add);
+
+    assert_eq!(red(&buffer, WIDTH, 10, 10), 128);
+    assert_eq!(red(&buffer, WIDTH, 0, 10), 128);
+    assert_eq!(red(&buffer, WIDTH, 0, 0), 0);
+    assert_eq!(red(&buffer, WIDTH, 19, 19), 0);
+}
+
+#[test]
+fn outline() {
+    let triangle = Polygon::triangle(
+        Point::new(2.0, 2.0),
+        Point::new(16.0, 2.0),
+        Point::new(2.0, 16.0),
+        false,
+    );
+
+    let mut buffer = black(WIDTH, HEIGHT);
+    canvas::polygon::render_outline(&mut buffer, WIDTH, HEIGHT, &triangle, &RED, Blend::Normal);
+
+    assert!(red(&buffer, WIDTH, 8, 2) > 0);
+    assert!(red(&buffer, WIDTH, 2, 8) > 0);
+    assert!(red(&buffer, WIDTH, 9, 9) > 0);
+    assert_eq!(red(&buffer, WIDTH, 5, 5), 0);
+}
+
+#[test]
+fn graphic_polygon() {
+    let triangle = Polygon::triangle(
+        Point::new(0.0, 0.0),
+        Point::new(6.0, 0.0),
+        Point::new(0.0, 6.0),
+        true,
+    );
+
+    let mut graphic = Graphic::polygon(triangle, RED);
+    graphic.set_x_y(10.0, 10.0);
+
+    let mut buffer = black(WIDTH, HEIGHT);
+    canvas::graphics::render(&mut buffer, WIDTH, HEIGHT, &vec![&graphic]);
+
+    assert!(red(&buffer, WIDTH, 11, 11) > 0);
+    assert_eq!(red(&buffer, WIDTH, 1, 1), 0);
+}
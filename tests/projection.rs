@@ -0,0 +1,144 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use common::{assert_golden, render_pose, Pose};
+
+use ray_casting_prototype::app::detection::cast_ray;
+use ray_casting_prototype::app::graphics::{angle_column, column_angle};
+use ray_casting_prototype::app::level::Level;
+use ray_casting_prototype::app::logic::update_state;
+use ray_casting_prototype::app::state::{Projection, State};
+
+const MAP: &str = "size 30 10
+unit 10
+spawn 15 5 107
+
+grid
+111111111111111111111111111111
+1............................1
+1............................1
+1............................1
+1............................1
+1............................1
+1............................1
+1............................1
+1............................1
+111111111111111111111111111111
+";
+
+fn state(projection: Projection) -> State {
+    let mut state = State::new();
+    state.apply(&Level::parse(MAP).unwrap());
+    state.init();
+    state.conf.projection = projection;
+    update_state(&mut state, 0.0, 0.0, 0.0);
+    state
+}
+
+// Projected wall height of the nearest hit per surface column
+fn wall_heights(state: &State) -> Vec<f32> {
+    let map = state.env.map;
+
+    (0..state.conf.resolution)
+        .map(|index| {
+            let rays = cast_ray(
+                state.sub.x,
+                state.sub.y,
+                map.width,
+                map.height,
+                state.sub.direction,
+                column_angle(state, index as f32),
+                state.env.height as f32,
+                state.env.unit,
+                &state.conf.grid,
+                &state.doors,
+                &state.segments,
+                &state.conf.heights,
+            );
+
+            rays[0].height
+        })
+        .collect()
+}
+
+// Largest distance of the heights from the line through the first and last column,
+// relative to the height
+fn bend(heights: &[f32]) -> f32 {
+    let (first, last) = (heights[0], heights[heights.len() - 1]);
+    let steps = (heights.len() - 1) as f32;
+
+    heights
+        .iter()
+        .enumerate()
+        .map(|(index, height)| {
+            let line = first + (last - first) * index as f32 / steps;
+            (height - line).abs() / height
+        })
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn compare_wall_heights() {
+    let angular = wall_heights(&state(Projection::Angular));
+    let plane = wall_heights(&state(Projection::CameraPlane));
+
+    assert_eq!(angular.len(), plane.len());
+
+    // Both models agree at the center and at the edges of the view
+    let center = angular.len() / 2;
+    assert!((angular[center] - plane[center]).abs() < 1e-3 * angular[center]);
+    assert!((angular[0] - plane[0]).abs() < 1e-3 * angular[0]);
+
+    // The view slants across the south wall, which recedes towards the right edge
+    assert!(plane[0] > plane[plane.len() - 1]);
+
+    // On the camera plane a straight wall projects with heights linear in the column,
+    // while constant angular steps bend it
+    assert!(bend(&plane) < 1e-5, "plane bend {}", bend(&plane));
+    assert!(bend(&angular) > 1e-3, "angular bend {}", bend(&angular));
+}
+
+#[test]
+fn column_round_trip() {
+    for projection in [Projection::Angular, Projection::CameraPlane] {
+        let state = state(projection);
+
+        for index in [0.0, 100.0, 480.0, 959.0] {
+            let relative = column_angle(&state, index) - state.sub.direction;
+            let relative = (relative + PI).rem_euclid(PI * 2.0) - PI;
+
+            assert!((angle_column(&state, relative) - index).abs() < 1e-2);
+        }
+    }
+}
+
+#[test]
+fn camera_plane_scene() {
+    let mut state = State::build();
+    state.conf.projection = Projection::CameraPlane;
+
+    let image = render_pose(&mut state, Pose::new(295.0, 225.0, PI * 1.25));
+    assert_golden("camera_plane", &image, 2);
+}
+
+// Rays are cast around the heading after this update's rotation
+#[test]
+fn middle_ray_follows_rotation() {
+    for projection in [Projection::Angular, Projection::CameraPlane] {
+        let mut state = state(projection);
+
+        state.sub.rotate_direction = 1.0;
+        state.conf.look = 0.3;
+        update_state(&mut state, 0.1, 0.1, 10.0);
+
+        let middle = state.conf.resolution as usize / 2;
+        let nearest = state
+            .frame
+            .nearest(middle)
+            .expect("Middle column should hit a wall");
+        let difference = (nearest.angle - state.sub.direction).sin().abs();
+
+        assert!(difference < 1e-4, "{projection:?} {difference}");
+    }
+}
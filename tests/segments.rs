@@ -0,0 +1,195 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use common::{assert_golden, render_pose, syntax, Pose};
+
+use ray_casting_prototype::app::detection::{circle_collision, ray_collision};
+use ray_casting_prototype::app::level::Level;
+use ray_casting_prototype::app::state::{Orientation, Segment, State};
+use ray_casting_prototype::graphics::line::Line;
+
+const UNIT: f32 = 10.0;
+
+const MAP: &str = "size 4 4
+unit 10
+spawn 0.5 0.5 0
+
+wall 1 0 4 3 2
+pillar 2 3 1 3
+
+grid
+....
+....
+....
+....
+";
+
+#[test]
+fn line_intersect_and_distance() {
+    let line = Line::new(0.0, 0.0, 10.0, 10.0);
+
+    assert!((line.length() - 200f32.sqrt()).abs() < 1e-4);
+
+    let (distance, position) = line.intersect(0.0, 10.0, 1.0, 0.0).unwrap();
+    assert!((distance - 10.0).abs() < 1e-4);
+    assert!((position - 1.0).abs() < 1e-4);
+
+    let (distance, position) = line.intersect(10.0, 0.0, -1.0, 0.0).unwrap();
+    assert!((distance - 10.0).abs() < 1e-4);
+    assert!(position.abs() < 1e-4);
+
+    assert!(line.intersect(0.0, 10.0, -1.0, 0.0).is_none());
+    assert!(line.intersect(0.0, 5.0, 1.0, 1.0).is_none());
+
+    assert!((line.distance(0.0, 10.0) - 50f32.sqrt()).abs() < 1e-4);
+    assert!((line.distance(-3.0, -4.0) - 5.0).abs() < 1e-4);
+}
+
+#[test]
+fn diagonal_wall() {
+    let grid = vec![vec![0; 4]; 4];
+    let segments = vec![Segment::new(Line::new(10.0, 0.0, 40.0, 30.0), 2)];
+
+    // Walking east along y = 5 meets the wall at x = 15
+    let collision = ray_collision(5.0, 5.0, 40.0, 40.0, 0.0, UNIT, &grid, &[], &segments);
+
+    assert_eq!(collision.id, 2);
+    assert!((collision.distance - 10.0).abs() < 1e-4);
+    assert!((collision.x - 15.0).abs() < 1e-4 && (collision.y - 5.0).abs() < 1e-4);
+    assert_eq!((collision.cell_x, collision.cell_y), (1, 0));
+    assert!((0.0..1.0).contains(&collision.offset));
+
+    // Walking south meets it perpendicular to the grid, as a horizontal face
+    let collision = ray_collision(35.0, 5.0, 40.0, 40.0, PI / 2.0, UNIT, &grid, &[], &segments);
+    assert!((collision.distance - 20.0).abs() < 1e-4);
+
+    // Rays pointing away never hit it
+    let collision = ray_collision(5.0, 5.0, 40.0, 40.0, PI, UNIT, &grid, &[], &segments);
+    assert_eq!(collision.id, -1);
+    assert_eq!(collision.orientation, Orientation::None);
+}
+
+#[test]
+fn nearest_of_grid_and_segments() {
+    let grid = vec![vec![0, 0, 0, 1]];
+    let near = vec![Segment::new(Line::new(20.0, 0.0, 20.0, 10.0), 2)];
+    let far = vec![Segment::new(Line::new(35.0, 0.0, 35.0, 10.0), 2)];
+
+    let collision = ray_collision(5.0, 5.0, 40.0, 10.0, 0.0, UNIT, &grid, &[], &near);
+    assert_eq!(collision.id, 2);
+    assert!((collision.distance - 15.0).abs() < 1e-4);
+    assert_eq!(collision.orientation, Orientation::Right);
+
+    let collision = ray_collision(5.0, 5.0, 40.0, 10.0, 0.0, UNIT, &grid, &[], &far);
+    assert_eq!(collision.id, 1);
+    assert!((collision.distance - 25.0).abs() < 1e-4);
+}
+
+#[test]
+fn thin_wall_blocks() {
+    let grid = vec![vec![0; 4]; 4];
+    let segments = vec![Segment::new(Line::new(20.0, 0.0, 20.0, 40.0), 1)];
+
+    assert!(!circle_collision(
+        17.0,
+        15.0,
+        2.0,
+        40.0,
+        40.0,
+        UNIT,
+        &grid,
+        &[],
+        &segments
+    ));
+    assert!(circle_collision(
+        18.5,
+        15.0,
+        2.0,
+        40.0,
+        40.0,
+        UNIT,
+        &grid,
+        &[],
+        &segments
+    ));
+    assert!(circle_collision(
+        21.0,
+        15.0,
+        2.0,
+        40.0,
+        40.0,
+        UNIT,
+        &grid,
+        &[],
+        &segments
+    ));
+}
+
+#[test]
+fn parse_segments() {
+    let level = Level::parse(MAP).unwrap();
+
+    assert_eq!(level.segments.len(), 5);
+    assert_eq!(level.segments[0].line, Line::new(1.0, 0.0, 4.0, 3.0));
+    assert_eq!(level.segments[0].id, 2);
+    assert!(level.segments[1..].iter().all(|segment| segment.id == 3));
+    assert!(level.segments[1..]
+        .iter()
+        .all(|segment| (segment.line.length() - 1.0).abs() < 1e-6));
+
+    // Segments are scaled to world units
+    let mut state = State::new();
+    state.apply(&level);
+    assert_eq!(state.segments[0].line, Line::new(10.0, 0.0, 40.0, 30.0));
+
+    let json = r#"{
+  "size": [4, 4],
+  "spawn": { "x": 0.5, "y": 0.5, "direction": 0 },
+  "walls": [{ "line": [1, 0, 4, 3], "tile": 2 }],
+  "pillars": [{ "x": 2, "y": 3, "size": 1, "tile": 3 }],
+  "grid": ["....", "....", "....", "...."]
+}"#;
+
+    let level = Level::parse_json(json).unwrap();
+    assert_eq!(level.segments.len(), 5);
+    assert_eq!(level.segments[0].line, Line::new(1.0, 0.0, 4.0, 3.0));
+}
+
+#[test]
+fn segment_errors() {
+    let source = MAP.replace("wall 1 0 4 3 2", "wall 1 0 5 3 2");
+    assert_eq!(
+        syntax(Level::parse(&source)),
+        (5, 1, "wall must be inside the grid".to_string())
+    );
+
+    let source = MAP.replace("wall 1 0 4 3 2", "wall 1 1 1 1 2");
+    assert_eq!(syntax(Level::parse(&source)).2, "wall must not be empty");
+
+    let source = MAP.replace("wall 1 0 4 3 2", "wall 1 0 4 3 0");
+    assert_eq!(syntax(Level::parse(&source)).2, "wall tile must not be 0");
+
+    let source = MAP.replace("pillar 2 3 1 3", "pillar 2 3 1");
+    assert_eq!(syntax(Level::parse(&source)).0, 6);
+
+    let json = r#"{
+  "size": [4, 4],
+  "spawn": { "x": 0.5, "y": 0.5, "direction": 0 },
+  "walls": [
+    { "line": [1, 0, 4, 3], "tile": 2 },
+    { "line": [1, 0, 4, 9], "tile": 2 }
+  ],
+  "grid": ["....", "....", "....", "...."]
+}"#;
+
+    let (line, column, _) = syntax(Level::parse_json(json));
+    assert_eq!((line, column), (6, 5));
+}
+
+#[test]
+fn segment_room() {
+    let mut state = State::load("maps/segments.map").unwrap();
+    let image = render_pose(&mut state, Pose::new(25.0, 60.0, 0.0));
+    assert_golden("segment_room", &image, 2);
+}
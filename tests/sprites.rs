@@ -0,0 +1,67 @@
+use ray_casting_prototype::app::graphics::draw_sprites;
+use ray_casting_prototype::app::level::Level;
+use ray_casting_prototype::app::logic::update_state;
+use ray_casting_prototype::app::state::{Sprite, State};
+use ray_casting_prototype::graphics::texture::Texture;
+
+const MAP: &str = "size 30 10
+unit 10
+spawn 15 5 0
+
+grid
+111111111111111111111111111111
+1............................1
+1............................1
+1............................1
+1............................1
+1............................1
+1............................1
+1............................1
+1............................1
+111111111111111111111111111111
+";
+
+// Subject in an empty room facing east, with a single red sprite at an angle and distance
+fn state(relative: f32, distance: f32, scale: f32) -> State {
+    let mut state = State::new();
+    state.apply(&Level::parse(MAP).unwrap());
+    state.init();
+
+    let (x, y) = (state.sub.x, state.sub.y);
+
+    state.sprites = vec![Sprite::new(
+        x + distance * relative.cos(),
+        y + distance * relative.sin(),
+        scale,
+        0,
+    )];
+    state.textures.sprites = vec![Texture::new(1, 1, vec![255, 0, 0, 255])];
+
+    update_state(&mut state, 0.0, 0.0, 0.0);
+    state
+}
+
+// Whether any sprite pixel was drawn
+fn drawn(state: &mut State) -> bool {
+    let (width, height) = (state.env.width, state.env.height);
+    let mut buffer = vec![0u8; (width * height * 4) as usize];
+
+    draw_sprites(state, &mut buffer, width, height);
+
+    buffer.chunks_exact(4).any(|pixel| pixel[0] == 255)
+}
+
+#[test]
+fn sprite_just_outside_view_edge() {
+    let edge = State::new().conf.fov / 2.0;
+
+    // Narrow sprite centered past the edge is culled
+    assert!(!drawn(&mut state(edge + 0.05, 30.0, 0.2)));
+
+    // Wide sprite centered past the edge still reaches into view
+    assert!(drawn(&mut state(edge + 0.05, 30.0, 2.0)));
+    assert!(drawn(&mut state(-edge - 0.05, 30.0, 2.0)));
+
+    // Close wide sprite centered beyond the whole field of view
+    assert!(drawn(&mut state(edge * 2.0 + 0.05, 20.0, 4.0)));
+}
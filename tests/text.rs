@@ -0,0 +1,141 @@
+mod common;
+
+use common::{assert_golden, black, lit, red, RED};
+
+use ray_casting_prototype::app::logic::{render_graphics, update_state};
+use ray_casting_prototype::app::state::State;
+use ray_casting_prototype::canvas;
+use ray_casting_prototype::graphics::color::Blend;
+use ray_casting_prototype::graphics::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use ray_casting_prototype::graphics::graphic::Graphic;
+use ray_casting_prototype::graphics::text::{Align, Text};
+
+const WIDTH: u32 = 40;
+const HEIGHT: u32 = 20;
+
+#[test]
+fn unknown_characters_fall_back() {
+    assert_eq!(font::glyph('\u{e9}'), font::glyph(font::REPLACEMENT));
+    assert_eq!(font::glyph('\t'), font::glyph(font::REPLACEMENT));
+    assert_ne!(font::glyph('A'), font::glyph(font::REPLACEMENT));
+    assert!(font::glyph(' ').iter().all(|row| *row == 0));
+}
+
+#[test]
+fn measures_lines() {
+    let text = Text::new(0.0, 0.0, "AB\nC\n", 1, Align::Left);
+
+    assert_eq!(text.lines().collect::<Vec<_>>(), vec!["AB", "C", ""]);
+    assert_eq!(text.measure("AB"), 11.0);
+    assert_eq!(text.measure(""), 0.0);
+    assert_eq!(text.size(), (11.0, 25.0));
+
+    let scaled = Text::new(0.0, 0.0, "AB", 3, Align::Left);
+
+    assert_eq!(scaled.size(), (33.0, 21.0));
+}
+
+#[test]
+fn aligns_lines() {
+    let left = Text::new(20.0, 0.0, "AAA\nA", 1, Align::Left);
+    let center = Text::new(20.0, 0.0, "AAA\nA", 1, Align::Center);
+    let right = Text::new(20.0, 0.0, "AAA\nA", 1, Align::Right);
+
+    assert_eq!((left.left("AAA"), left.left("A")), (20.0, 20.0));
+    assert_eq!((center.left("AAA"), center.left("A")), (11.0, 17.0));
+    assert_eq!((right.left("AAA"), right.left("A")), (3.0, 15.0));
+
+    let bounds = right.bounds();
+
+    assert_eq!((bounds.x, bounds.width), (3.0, 17.0));
+    assert!(right.contains(10.0, 3.0));
+    assert!(!right.contains(21.0, 3.0));
+}
+
+// Every set font pixel is drawn as a scale by scale block and nothing else is drawn
+#[test]
+fn renders_glyphs() {
+    for scale in 1..=2 {
+        let mut buffer = black(WIDTH, HEIGHT);
+        let text = Text::new(3.0, 2.0, "7", scale, Align::Left);
+
+        canvas::text::render(&mut buffer, WIDTH, HEIGHT, &text, &RED, Blend::Normal);
+
+        let mut expected = Vec::new();
+
+        for y in 0..GLYPH_HEIGHT * scale {
+            for x in 0..GLYPH_WIDTH * scale {
+                if font::pixel('7', x / scale, y / scale) {
+                    expected.push((3 + x, 2 + y));
+                }
+            }
+        }
+
+        expected.sort_by_key(|(x, y)| (*y, *x));
+
+        assert_eq!(lit(&buffer, WIDTH, HEIGHT), expected, "scale {scale}");
+    }
+}
+
+#[test]
+fn renders_multiline_clipped() {
+    let mut buffer = black(WIDTH, HEIGHT);
+    let text = Text::new(-2.0, 8.0, "||||||||\n||", 1, Align::Left);
+
+    canvas::text::render(&mut buffer, WIDTH, HEIGHT, &text, &RED, Blend::Normal);
+
+    // The bar of each glyph sits in its middle column, lines start GLYPH_HEIGHT + LEADING apart
+    assert!(red(&buffer, WIDTH, 0, 8) > 0);
+    assert!(red(&buffer, WIDTH, 6, 14) > 0);
+    assert!(red(&buffer, WIDTH, 0, 17) > 0);
+    assert!(red(&buffer, WIDTH, 0, 19) > 0);
+    assert_eq!(red(&buffer, WIDTH, 0, 15), 0);
+    assert_eq!(red(&buffer, WIDTH, 12, 17), 0);
+}
+
+#[test]
+fn graphic_text() {
+    let mut buffer = black(WIDTH, HEIGHT);
+    let mut graphic = Graphic::text(Text::new(0.0, 0.0, "-", 1, Align::Left), RED);
+
+    graphic.set_x_y(10.0, 5.0);
+    graphic.set_blend(Blend::Add);
+
+    canvas::graphics::render(&mut buffer, WIDTH, HEIGHT, &vec![&graphic]);
+
+    assert_eq!(
+        lit(&buffer, WIDTH, HEIGHT),
+        (10..15).map(|x| (x, 8)).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn message_expires() {
+    let mut state = State::build();
+
+    state.show("Door locked");
+
+    update_state(&mut state, 1.0, 1.0, 60.0);
+
+    assert!(state.message.visible());
+
+    update_state(&mut state, 3.0, 2.0, 60.0);
+
+    assert!(!state.message.visible());
+}
+
+// Status panel in the top left corner and a two line message centered at the top
+#[test]
+fn overlay_matches_golden() {
+    let mut state = State::load("maps/default.map").unwrap();
+    let (width, height) = (state.env.width, state.env.height);
+
+    state.show("Overlay message\nSecond line");
+    update_state(&mut state, 0.0, 0.0, 60.0);
+
+    let mut buffer = vec![0u8; (width * height * 4) as usize];
+    render_graphics(&mut state, &mut buffer, width, height);
+
+    let image = image::RgbaImage::from_raw(width, height, buffer).unwrap();
+    assert_golden("overlay", &image, 2);
+}
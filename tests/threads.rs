@@ -0,0 +1,86 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use common::{assert_golden, render_pose, Pose};
+
+use ray_casting_prototype::app::graphics::cast_rays;
+use ray_casting_prototype::app::logic::update_state;
+use ray_casting_prototype::app::state::{Projection, State};
+
+const MAPS: [&str; 4] = [
+    "maps/default.map",
+    "maps/textured.map",
+    "maps/heights.map",
+    "maps/segments.map",
+];
+
+// Thread counts that split the surface into uneven bands
+const THREADS: [usize; 3] = [2, 7, 64];
+
+fn state(path: &str, threads: usize) -> State {
+    let mut state = State::load(path).expect("Map should load");
+    state.conf.threads = threads;
+    state
+}
+
+fn spawn(state: &State) -> Pose {
+    Pose::new(state.sub.x, state.sub.y, state.sub.direction + 0.3)
+}
+
+#[test]
+fn frames_match_single_thread() {
+    for path in MAPS {
+        let mut single = state(path, 1);
+        let pose = spawn(&single);
+        let expected = render_pose(&mut single, pose);
+
+        for threads in THREADS {
+            let actual = render_pose(&mut state(path, threads), pose);
+
+            assert!(
+                expected == actual,
+                "Frame of {path} on {threads} threads differs from a single thread"
+            );
+        }
+    }
+}
+
+#[test]
+fn frame_matches_golden() {
+    let mut state = State::build();
+    state.conf.threads = 4;
+    let image = render_pose(&mut state, Pose::new(120.0, 40.0, PI / 6.0));
+    assert_golden("spawn", &image, 0);
+}
+
+#[test]
+fn frames_match_with_pitch_and_plane_projection() {
+    let mut single = state("maps/heights.map", 1);
+    single.conf.projection = Projection::CameraPlane;
+    single.sub.pitch = 0.3;
+    let pose = spawn(&single);
+    let expected = render_pose(&mut single, pose);
+
+    let mut threaded = state("maps/heights.map", 5);
+    threaded.conf.projection = Projection::CameraPlane;
+    threaded.sub.pitch = 0.3;
+
+    assert!(expected == render_pose(&mut threaded, pose));
+}
+
+#[test]
+fn rays_match_single_thread() {
+    let mut single = state("maps/segments.map", 1);
+    update_state(&mut single, 0.0, 0.0, 0.0);
+    let expected = cast_rays(&single);
+
+    assert_eq!(expected.len(), single.conf.resolution as usize);
+
+    for threads in THREADS {
+        let mut threaded = state("maps/segments.map", threads);
+        update_state(&mut threaded, 0.0, 0.0, 0.0);
+
+        assert_eq!(expected, cast_rays(&threaded));
+    }
+}